@@ -0,0 +1,71 @@
+//! DES核心基准测试
+//!
+//! 运行: cargo bench --bench des_bench
+//! 对比查表子密钥生成: cargo bench --bench des_bench --features fast-keyschedule
+//!
+//! `one_shot` 每个块都重新编排密钥（`encrypt` 内部依次调用 `deskey`、`des`、`clear_key`），
+//! `reuse_schedule` 只编排一次密钥后连续处理多个块，两者之差即密钥编排的开销
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use vnc_des::{VncAuth, VncDesEngine, VncDesProcessor};
+
+const KEY: [u8; 8] = [0x17, 0x52, 0x6b, 0x06, 0x23, 0x4e, 0x58, 0x07];
+const BLOCKS: usize = 1000;
+
+fn bench_block(c: &mut Criterion) {
+    let mut group = c.benchmark_group("des_block");
+    group.throughput(Throughput::Elements(BLOCKS as u64));
+    let blocks: Vec<[u8; 8]> = (0..BLOCKS as u64).map(u64::to_be_bytes).collect();
+
+    group.bench_function("one_shot", |b| {
+        let mut engine = VncDesEngine::new();
+        let mut output = [0u8; 8];
+        b.iter(|| {
+            for block in &blocks {
+                engine
+                    .encrypt(&mut output, black_box(block), black_box(&KEY))
+                    .unwrap();
+                black_box(output);
+            }
+        })
+    });
+
+    group.bench_function("reuse_schedule", |b| {
+        let mut engine = VncDesEngine::new();
+        let mut output = [0u8; 8];
+        b.iter(|| {
+            engine.deskey(black_box(&KEY), true);
+            for block in &blocks {
+                engine.des(black_box(block), &mut output);
+                black_box(output);
+            }
+            engine.clear_key();
+        })
+    });
+
+    group.finish();
+}
+
+fn bench_key_schedule(c: &mut Criterion) {
+    let mut engine = VncDesEngine::new();
+
+    c.bench_function("des_key_schedule", |b| {
+        b.iter(|| engine.deskey(black_box(&KEY), black_box(true)))
+    });
+}
+
+fn bench_end_to_end(c: &mut Criterion) {
+    let processor = VncDesProcessor::default();
+    c.bench_function("end_to_end/encrypt_password", |b| {
+        b.iter(|| processor.encrypt_password(black_box("password")).unwrap())
+    });
+
+    // 服务器认证场景：每个连接一次挑战-响应
+    let challenge = [0x5Au8; 16];
+    c.bench_function("end_to_end/challenge_response", |b| {
+        b.iter(|| VncAuth::respond(black_box("password"), black_box(&challenge)))
+    });
+}
+
+criterion_group!(benches, bench_block, bench_key_schedule, bench_end_to_end);
+criterion_main!(benches);
@@ -0,0 +1,121 @@
+//! VNC DES处理器基准测试
+//!
+//! 运行: cargo bench --bench processor_bench
+//! 对比栈上存储版本: cargo bench --bench processor_bench --features smallvec
+//! 对比查表子密钥生成: cargo bench --bench processor_bench --features fast-keyschedule -- deskey
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use vnc_des::{VncDesConfig, VncDesEngine, VncDesProcessor};
+
+/// 统计堆分配次数的分配器
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// 统计单次加密产生的堆分配次数
+fn allocations_per_encrypt(processor: &VncDesProcessor, password: &str) -> usize {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    let encrypted = processor.encrypt_password(password).unwrap();
+    let after = ALLOCATIONS.load(Ordering::Relaxed);
+    drop(encrypted);
+    after - before
+}
+
+fn bench_encrypt_password(c: &mut Criterion) {
+    let processor = VncDesProcessor::default();
+    let truncating = VncDesProcessor::new(VncDesConfig::new().with_auto_truncate(true));
+
+    println!(
+        "堆分配次数/次加密: 短密码 = {}, 截断密码 = {}",
+        allocations_per_encrypt(&processor, "secret"),
+        allocations_per_encrypt(&truncating, "verylongpassword"),
+    );
+
+    c.bench_function("encrypt_password/short", |b| {
+        b.iter(|| processor.encrypt_password(black_box("secret")).unwrap())
+    });
+
+    c.bench_function("encrypt_password/truncated", |b| {
+        b.iter(|| {
+            truncating
+                .encrypt_password(black_box("verylongpassword"))
+                .unwrap()
+        })
+    });
+}
+
+fn bench_encrypt_iter(c: &mut Criterion) {
+    let processor = VncDesProcessor::default();
+    let passwords: Vec<String> = (0..1000).map(|i| format!("pw{:05}", i)).collect();
+
+    c.bench_function("encrypt_many/loop_1000", |b| {
+        b.iter(|| {
+            for password in &passwords {
+                black_box(VncDesProcessor::to_hex_string(
+                    &processor.encrypt_password(password).unwrap(),
+                ));
+            }
+        })
+    });
+
+    c.bench_function("encrypt_many/iter_1000", |b| {
+        b.iter(|| {
+            for hex in processor.encrypt_iter(passwords.iter().map(String::as_str)) {
+                black_box(hex.unwrap());
+            }
+        })
+    });
+}
+
+fn bench_encrypt_blocks(c: &mut Criterion) {
+    let processor = VncDesProcessor::default();
+    // 常见场景：16字节（2个块）的挑战
+    let challenge = [0x5Au8; 16];
+
+    c.bench_function("encrypt_blocks/vec", |b| {
+        b.iter(|| processor.encrypt_blocks(black_box(&challenge)).unwrap())
+    });
+
+    #[cfg(feature = "smallvec")]
+    c.bench_function("encrypt_blocks/smallvec", |b| {
+        b.iter(|| {
+            processor
+                .encrypt_blocks_small(black_box(&challenge))
+                .unwrap()
+        })
+    });
+}
+
+fn bench_deskey(c: &mut Criterion) {
+    let mut engine = VncDesEngine::new();
+    let key = [0x17, 0x52, 0x6b, 0x06, 0x23, 0x4e, 0x58, 0x07];
+
+    c.bench_function("deskey", |b| {
+        b.iter(|| engine.deskey(black_box(&key), black_box(true)))
+    });
+}
+
+criterion_group!(
+    benches,
+    bench_encrypt_password,
+    bench_encrypt_iter,
+    bench_encrypt_blocks,
+    bench_deskey
+);
+criterion_main!(benches);
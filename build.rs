@@ -0,0 +1,45 @@
+//! 构建脚本
+//!
+//! 启用 `ffi` 特性时使用 cbindgen 根据 `src/ffi.rs` 生成C头文件到 `OUT_DIR`，构建过程不修改源码目录。
+//! 仓库中的 `include/vnc_des.h` 通过 `make header` 更新（设置 `VNC_DES_HEADER_OUT` 指定额外的输出路径），
+//! 与生成结果不一致时构建会给出警告
+
+fn main() {
+    #[cfg(feature = "ffi")]
+    generate_header();
+}
+
+#[cfg(feature = "ffi")]
+fn generate_header() {
+    use std::path::PathBuf;
+
+    println!("cargo:rerun-if-changed=src/ffi.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    println!("cargo:rerun-if-changed=include/vnc_des.h");
+    println!("cargo:rerun-if-env-changed=VNC_DES_HEADER_OUT");
+
+    let crate_dir = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap());
+    let out_dir = PathBuf::from(std::env::var("OUT_DIR").unwrap());
+    let config = cbindgen::Config::from_file(crate_dir.join("cbindgen.toml"))
+        .expect("无法读取 cbindgen.toml");
+
+    let bindings = cbindgen::Builder::new()
+        .with_config(config)
+        .with_src(crate_dir.join("src/ffi.rs"))
+        .generate()
+        .expect("无法生成C头文件");
+
+    let generated = out_dir.join("vnc_des.h");
+    bindings.write_to_file(&generated);
+
+    if let Some(path) = std::env::var_os("VNC_DES_HEADER_OUT") {
+        bindings.write_to_file(path);
+        return;
+    }
+
+    // 只读取源码目录中的头文件，检查是否需要重新生成
+    let checked_in = std::fs::read(crate_dir.join("include/vnc_des.h")).ok();
+    if checked_in != std::fs::read(&generated).ok() {
+        println!("cargo:warning=include/vnc_des.h 与 src/ffi.rs 不一致，请运行 make header 更新");
+    }
+}
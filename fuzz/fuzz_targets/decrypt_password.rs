@@ -0,0 +1,24 @@
+//! 模糊测试：任意字节输入 `decrypt_password` 只返回错误，不会panic
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use vnc_des::{VncDesError, VncDesProcessor};
+
+fuzz_target!(|data: &[u8]| {
+    let processor = VncDesProcessor::default();
+
+    match processor.decrypt_password(data) {
+        Ok(password) => {
+            assert_eq!(data.len(), 8);
+            assert!(password.len() <= 8);
+        }
+        // 长度错误在解密前被拒绝
+        Err(VncDesError::InvalidPasswordFormat(_)) => assert_ne!(data.len(), 8),
+        // 8字节密文解密出无效UTF-8时走错误路径
+        Err(VncDesError::DecryptionFailed(_)) => {
+            let bytes = processor.decrypt_password_bytes(data).unwrap();
+            assert!(std::str::from_utf8(&bytes).is_err());
+        }
+        Err(e) => panic!("意外的错误: {}", e),
+    }
+});
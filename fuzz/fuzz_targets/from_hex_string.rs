@@ -0,0 +1,13 @@
+//! 模糊测试：任意字符串输入 `from_hex_string` 不会panic
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use vnc_des::VncDesProcessor;
+
+fuzz_target!(|data: &str| {
+    if let Ok(bytes) = VncDesProcessor::from_hex_string(data) {
+        // 解析成功的结果重新编码后应能原样解析
+        let hex = VncDesProcessor::to_hex_string(&bytes);
+        assert_eq!(VncDesProcessor::from_hex_string(&hex).unwrap(), bytes);
+    }
+});
@@ -0,0 +1,43 @@
+/* VNC DES C接口。此文件由 cbindgen 自动生成，请勿手动修改。 */
+
+#ifndef VNC_DES_H
+#define VNC_DES_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+// 成功
+#define VNC_DES_OK 0
+
+// 必需的指针参数为 `NULL`
+#define VNC_DES_ERR_NULL_POINTER -1
+
+// 长度无效（密码为空，或密文不是8字节）
+#define VNC_DES_ERR_INVALID_LENGTH -2
+
+// 加密VNC密码
+//
+// 密码超过8字节时截断，不足时补0，与标准VNC实现一致。
+//
+// # Safety
+//
+// - `pw` 必须指向至少 `len` 个可读字节
+// - `key` 必须指向8个可读字节
+// - `out` 必须指向8个可写字节，且不与 `pw`、`key` 重叠
+int32_t vnc_des_encrypt(const uint8_t *pw, size_t len, const uint8_t *key, uint8_t *out);
+
+// 解密VNC密码
+//
+// 输出为补0后的8字节明文，第一个0字节之前的部分即密码（8字节密码没有结尾的0）。
+//
+// # Safety
+//
+// - `enc` 必须指向至少 `len` 个可读字节，且 `len` 必须为8
+// - `key` 必须指向8个可读字节
+// - `out` 必须指向8个可写字节，且不与 `enc`、`key` 重叠
+int32_t vnc_des_decrypt(const uint8_t *enc,
+                        size_t len,
+                        const uint8_t *key,
+                        uint8_t *out);
+
+#endif /* VNC_DES_H */
@@ -6,27 +6,76 @@
 //!
 //! 用法:
 //!   vnc_des_tool encrypt <PASSWORD>                    # 加密明文密码为16进制
+//!   vnc_des_tool encrypt <PASSWORD>...                 # 加密多个密码，每行一个结果
 //!   vnc_des_tool decrypt <HEX_PASSWORD>                # 解密16进制密码为明文
+//!   vnc_des_tool decrypt --in-file <PATH>              # 解密文件中的密码（二进制passwd文件或文本）
 //!   vnc_des_tool verify <PASSWORD> <HEX_PASSWORD>      # 验证密码是否匹配
+//!   vnc_des_tool encrypt --stdin                       # 从标准输入读取密码（也可将 PASSWORD 写为 -）
+//!   vnc_des_tool verify --stdin <HEX_PASSWORD>         # 从标准输入读取密码并验证
+//!   vnc_des_tool verify --batch <FILE>                 # 批量验证文件中的 明文<TAB>16进制密码 行
 //!   vnc_des_tool demo [PASSWORD]                       # 演示加解密功能
+//!   vnc_des_tool validate-hex <HEX_PASSWORD>           # 仅校验16进制密文格式
+//!   vnc_des_tool identify <HEX_PASSWORD>               # 用各VNC实现的已知密钥尝试解密
+//!   vnc_des_tool dump-vectors [--count N]              # 输出 明文<TAB>16进制密码 测试向量
+//!   vnc_des_tool diagnose <PASSWORD> [--json]          # 输出用于问题报告的诊断信息
+//!   vnc_des_tool completions <SHELL>                   # 输出 bash/zsh/fish 等shell的补全脚本
 //!
 //! 密钥选项:
 //!   --key <HEX_KEY>                                     # 使用自定义16进制密钥
 //!   --key-file <FILE>                                   # 从配置文件读取密钥
+//!   --key-stdin                                         # 从标准输入读取16进制密钥（首行）
+//!   --key-env <VAR_NAME>                                # 从环境变量读取16进制密钥
+//!                                                       # （未指定任何密钥选项和 --product 时自动读取 VNC_DES_KEY）
+//!   --product <PRODUCT>                                 # 使用指定VNC产品的默认密钥
+//!                                                       # （别名 --vnc-flavor，优先级低于 --key 等）
+//!
+//! 其他全局选项:
+//!   --timing-safe                                       # decrypt --compare 使用常量时间比较（verify 始终使用）
+//!
+//! 子命令选项:
+//!   encrypt/decrypt --format <hex|base64>               # 密文的输出/输入编码（默认hex）
+//!   encrypt --hex-case <lower|upper>                    # 十六进制输出的大小写（默认lower）
 //!
 //! 示例:
 //!   vnc_des_tool encrypt "password123"
 //!   vnc_des_tool decrypt "33483fd570cf869b"
 //!   vnc_des_tool verify "password123" "33483fd570cf869b"
 //!   vnc_des_tool --key "0123456789abcdef" encrypt "test"
+//!
+//! 退出码:
+//!   0  操作成功（verify: 密码匹配；validate-hex: 格式有效；identify: 找到候选明文）
+//!   1  否定结果（verify/decrypt --compare: 不匹配（verify --batch: 任一行不匹配）；
+//!      validate-hex: 格式无效；identify: 没有候选明文；config --diff: 存在差异；
+//!      diagnose: 往返验证失败）
+//!   2  输入或运行错误（无效的16进制/长度、无效密钥、配置或IO错误、命令行参数错误；
+//!      verify --batch: 存在格式错误的行）
 
 use clap::{Arg, ArgMatches, Command};
+use clap_complete::Shell;
+use std::env;
+use std::io::{self, BufRead, IsTerminal};
 use std::process;
+use vnc_des::key_provider::DEFAULT_KEY_ENV_VAR;
+use vnc_des::passwd_file::PASSWD_BLOCK_SIZE;
 use vnc_des::{
-    info, version, PasswordProcessor, VncDesConfig, VncDesError, VncDesProcessor,
+    constant_time_eq, info, version, Encoding, EnvKeyProvider, HexCase, KeyProvider, KnownKey,
+    PasswordProcessor, VncDesConfig, VncDesError, VncDesProcessor, VncProduct,
     TIGHTVNC_DEFAULT_KEY,
 };
 
+/// 退出码：否定结果（密码不匹配、格式无效等）
+const EXIT_MISMATCH: i32 = 1;
+/// 退出码：输入或运行错误（与clap的参数错误退出码一致）
+const EXIT_ERROR: i32 = 2;
+
+/// `dump-vectors` 使用的内置密码（均不超过8字节，严格模式下也不会被拒绝）
+const DUMP_VECTOR_PASSWORDS: [&str; 8] = [
+    "password", "test", "12345678", "secret", "vnc", "abc123", "pass wd", "demo123",
+];
+
+/// `dump-vectors --count` 的上限：追加的 `pw{:06}` 形式的密码在此范围内不超过8字节，不会被截断
+const DUMP_VECTOR_MAX_COUNT: i64 = 1_000_000;
+
 fn main() {
     // 解析命令行参数
     let matches = build_cli().get_matches();
@@ -37,10 +86,15 @@ fn main() {
         Some(("decrypt", sub_matches)) => handle_decrypt(sub_matches),
         Some(("verify", sub_matches)) => handle_verify(sub_matches),
         Some(("demo", sub_matches)) => handle_demo(sub_matches),
+        Some(("validate-hex", sub_matches)) => handle_validate_hex(sub_matches),
+        Some(("identify", sub_matches)) => handle_identify(sub_matches),
+        Some(("dump-vectors", sub_matches)) => handle_dump_vectors(sub_matches),
         Some(("config", sub_matches)) => handle_config(sub_matches),
+        Some(("diagnose", sub_matches)) => handle_diagnose(sub_matches),
+        Some(("completions", sub_matches)) => handle_completions(sub_matches),
         _ => {
             eprintln!("❌ 未知命令，请使用 --help 查看帮助");
-            process::exit(1);
+            process::exit(EXIT_ERROR);
         }
     };
 
@@ -51,7 +105,7 @@ fn main() {
         }
         Err(e) => {
             eprintln!("❌ 操作失败: {}", e);
-            process::exit(1);
+            process::exit(EXIT_ERROR);
         }
     }
 }
@@ -80,6 +134,39 @@ fn build_cli() -> Command {
                 .long("key-file")
                 .value_name("FILE")
                 .help("从配置文件读取密钥")
+                .conflicts_with("key")
+                .global(true),
+        )
+        .arg(
+            Arg::new("key_stdin")
+                .long("key-stdin")
+                .help("从标准输入读取16进制密钥（读取首行并去除首尾空白）")
+                .action(clap::ArgAction::SetTrue)
+                .conflicts_with_all(["key", "key_file"])
+                .global(true),
+        )
+        .arg(
+            Arg::new("key_env")
+                .long("key-env")
+                .value_name("VAR_NAME")
+                .help("从指定环境变量读取16进制密钥（未指定任何密钥选项和 --product 时自动读取 VNC_DES_KEY）")
+                .conflicts_with_all(["key", "key_file", "key_stdin"])
+                .global(true),
+        )
+        .arg(
+            Arg::new("product")
+                .long("product")
+                .visible_alias("vnc-flavor")
+                .value_name("PRODUCT")
+                .help("使用指定VNC产品的默认密钥（优先级低于其他显式密钥选项，高于自动读取的 VNC_DES_KEY）")
+                .value_parser(["tightvnc", "realvnc", "ultravnc"])
+                .global(true),
+        )
+        .arg(
+            Arg::new("timing_safe")
+                .long("timing-safe")
+                .help("decrypt --compare 使用常量时间比较（verify 始终使用常量时间比较）")
+                .action(clap::ArgAction::SetTrue)
                 .global(true),
         )
         .arg(
@@ -97,17 +184,53 @@ fn build_cli() -> Command {
                 .long_about("将明文密码加密为VNC兼容的16进制格式，用于配置存储")
                 .arg(
                     Arg::new("password")
-                        .help("要加密的明文密码")
+                        .help("要加密的明文密码，可指定多个（为 - 时从标准输入读取）")
                         .value_name("PASSWORD")
-                        .required(true)
+                        .num_args(1..)
+                        .required_unless_present("stdin")
+                        .conflicts_with("stdin")
                         .index(1),
                 )
+                .arg(
+                    Arg::new("stdin")
+                        .long("stdin")
+                        .help("从标准输入读取明文密码（终端下不回显）")
+                        .action(clap::ArgAction::SetTrue),
+                )
                 .arg(
                     Arg::new("quiet")
                         .short('q')
                         .long("quiet")
                         .help("静默模式，仅输出结果")
                         .action(clap::ArgAction::SetTrue),
+                )
+                .arg(
+                    Arg::new("no_min")
+                        .long("no-min")
+                        .help("不检查最小密码长度")
+                        .action(clap::ArgAction::SetTrue),
+                )
+                .arg(
+                    Arg::new("format")
+                        .long("format")
+                        .value_name("FORMAT")
+                        .help("加密结果的输出编码")
+                        .value_parser(["hex", "base64"])
+                        .default_value("hex"),
+                )
+                .arg(
+                    Arg::new("hex_case")
+                        .long("hex-case")
+                        .value_name("CASE")
+                        .help("十六进制输出的大小写（仅 --format hex 时生效）")
+                        .value_parser(["lower", "upper"])
+                        .default_value("lower"),
+                )
+                .arg(
+                    Arg::new("qr")
+                        .long("qr")
+                        .help("以终端二维码形式输出结果（需要启用 qrcode 特性）")
+                        .action(clap::ArgAction::SetTrue),
                 ),
         )
         // 解密子命令
@@ -117,11 +240,32 @@ fn build_cli() -> Command {
                 .long_about("将16进制格式的加密密码解密为明文密码")
                 .arg(
                     Arg::new("hex_password")
-                        .help("16进制格式的加密密码（16个字符）")
+                        .help("16进制格式的加密密码（16个字符，--format base64 时为Base64）")
                         .value_name("HEX_PASSWORD")
-                        .required(true)
+                        .required_unless_present("in_file")
                         .index(1),
                 )
+                .arg(
+                    Arg::new("in_file")
+                        .long("in-file")
+                        .value_name("PATH")
+                        .help("从文件读取加密密码：8字节的二进制passwd文件（如 ~/.vnc/passwd）或文本文件")
+                        .conflicts_with("hex_password"),
+                )
+                .arg(
+                    Arg::new("compare")
+                        .long("compare")
+                        .value_name("PASSWORD")
+                        .help("将解密结果与给定明文比较（匹配退出码0，不匹配退出码1）"),
+                )
+                .arg(
+                    Arg::new("format")
+                        .long("format")
+                        .value_name("FORMAT")
+                        .help("加密密码的输入编码")
+                        .value_parser(["hex", "base64"])
+                        .default_value("hex"),
+                )
                 .arg(
                     Arg::new("quiet")
                         .short('q')
@@ -135,20 +279,37 @@ fn build_cli() -> Command {
             Command::new("verify")
                 .about("验证明文密码与16进制密码是否匹配")
                 .long_about("验证明文密码加密后是否与给定的16进制密码匹配")
+                .after_help(
+                    "退出码：0 匹配；1 不匹配（批量模式下任一行不匹配）；\
+                     2 错误（16进制格式或长度无效、密钥无效、读取失败等）",
+                )
                 .arg(
                     Arg::new("password")
-                        .help("明文密码")
+                        .help("明文密码（为 - 时从标准输入读取；使用 --stdin 时省略）")
                         .value_name("PASSWORD")
-                        .required(true)
+                        .required_unless_present("batch")
                         .index(1),
                 )
                 .arg(
                     Arg::new("hex_password")
                         .help("16进制格式的加密密码")
                         .value_name("HEX_PASSWORD")
-                        .required(true)
+                        .required_unless_present_any(["stdin", "batch"])
                         .index(2),
                 )
+                .arg(
+                    Arg::new("stdin")
+                        .long("stdin")
+                        .help("从标准输入读取明文密码（终端下不回显）")
+                        .action(clap::ArgAction::SetTrue),
+                )
+                .arg(
+                    Arg::new("batch")
+                        .long("batch")
+                        .help("批量验证文件中的 `明文<TAB>16进制密码` 行（忽略空行和 # 注释行）")
+                        .value_name("FILE")
+                        .conflicts_with_all(["password", "hex_password", "stdin"]),
+                )
                 .arg(
                     Arg::new("quiet")
                         .short('q')
@@ -157,6 +318,66 @@ fn build_cli() -> Command {
                         .action(clap::ArgAction::SetTrue),
                 ),
         )
+        // 十六进制校验子命令
+        .subcommand(
+            Command::new("validate-hex")
+                .about("校验16进制密码格式（不解密）")
+                .long_about(
+                    "规范化并检查16进制字符串是否为格式正确的8字节密文，无效时以非零状态退出",
+                )
+                .arg(
+                    Arg::new("hex_password")
+                        .help("要校验的16进制字符串")
+                        .value_name("HEX_PASSWORD")
+                        .required(true)
+                        .index(1),
+                ),
+        )
+        // 识别VNC实现子命令
+        .subcommand(
+            Command::new("identify")
+                .about("尝试用各VNC实现的已知密钥解密，判断密文来源")
+                .long_about(
+                    "依次使用每个已知VNC实现的默认密钥解密16进制密码，列出解密结果为可打印ASCII的实现及候选明文，\
+                     没有任何候选时以非零状态退出",
+                )
+                .arg(
+                    Arg::new("hex_password")
+                        .help("16进制格式的加密密码")
+                        .value_name("HEX_PASSWORD")
+                        .required(true)
+                        .index(1),
+                )
+                .arg(
+                    Arg::new("quiet")
+                        .short('q')
+                        .long("quiet")
+                        .help("静默模式，每行输出 `实现名称<TAB>候选明文`")
+                        .action(clap::ArgAction::SetTrue),
+                ),
+        )
+        // 测试向量导出子命令
+        .subcommand(
+            Command::new("dump-vectors")
+                .about("输出确定的测试向量（每行 `明文<TAB>16进制密码`）")
+                .long_about(
+                    "使用当前密钥加密一组固定的密码，输出可直接用于 `verify --batch` 的测试向量，\
+                     首行为记录密钥的 # 注释",
+                )
+                .arg(
+                    Arg::new("count")
+                        .long("count")
+                        .short('n')
+                        .value_name("N")
+                        .help(
+                            "输出的向量数量（默认为全部内置密码，超出时追加 pw000008 形式的密码，\
+                             最多1000000个）",
+                        )
+                        .value_parser(
+                            clap::value_parser!(u32).range(0..=DUMP_VECTOR_MAX_COUNT),
+                        ),
+                ),
+        )
         // 演示子命令
         .subcommand(
             Command::new("demo")
@@ -169,6 +390,28 @@ fn build_cli() -> Command {
                         .index(1),
                 ),
         )
+        // 诊断子命令
+        .subcommand(
+            Command::new("diagnose")
+                .about("输出用于问题报告的诊断信息")
+                .long_about(
+                    "输出版本、有效配置（密钥以指纹代替）、加密结果、子密钥表指纹及往返验证结果，\n\
+                     可直接复制到互通性问题报告中。输出中不包含明文密码及原始密钥",
+                )
+                .arg(
+                    Arg::new("password")
+                        .help("用于诊断的明文密码")
+                        .value_name("PASSWORD")
+                        .required(true)
+                        .index(1),
+                )
+                .arg(
+                    Arg::new("json")
+                        .long("json")
+                        .help("以JSON格式输出")
+                        .action(clap::ArgAction::SetTrue),
+                ),
+        )
         // 配置子命令
         .subcommand(
             Command::new("config")
@@ -180,17 +423,68 @@ fn build_cli() -> Command {
                         .help("显示当前配置")
                         .action(clap::ArgAction::SetTrue),
                 )
+                .arg(
+                    Arg::new("redact")
+                        .long("redact")
+                        .help("显示配置时隐藏密钥（以指纹代替）")
+                        .action(clap::ArgAction::SetTrue)
+                        .requires("show"),
+                )
+                .arg(
+                    Arg::new("security")
+                        .long("security")
+                        .help("显示当前配置的安全评估报告")
+                        .action(clap::ArgAction::SetTrue),
+                )
                 .arg(
                     Arg::new("generate")
                         .long("generate")
                         .value_name("FILE")
-                        .help("生成配置文件"),
+                        .help("生成配置文件（扩展名为 .toml 时生成TOML，需要启用 toml 特性）"),
                 )
                 .arg(
                     Arg::new("validate")
                         .long("validate")
                         .value_name("FILE")
                         .help("验证配置文件"),
+                )
+                .arg(
+                    Arg::new("diff")
+                        .long("diff")
+                        .value_names(["FILE_A", "FILE_B"])
+                        .num_args(2)
+                        .help("逐字段对比两个配置文件"),
+                )
+                .arg(
+                    Arg::new("expect_checksum")
+                        .long("expect-checksum")
+                        .value_name("SHA256")
+                        .help("验证配置文件时同时校验其SHA-256校验和")
+                        .requires("validate"),
+                )
+                .arg(
+                    Arg::new("compare_schedule")
+                        .long("compare-schedule")
+                        .value_names(["KEY_A", "KEY_B"])
+                        .num_args(2)
+                        .help("对比两个密钥的DES子密钥表（需要 debug-internals 特性）"),
+                ),
+        )
+        // 补全脚本子命令
+        .subcommand(
+            Command::new("completions")
+                .about("生成shell补全脚本")
+                .long_about(
+                    "将指定shell的补全脚本输出到标准输出，例如：\n\
+                     vnc_des_tool completions bash > /etc/bash_completion.d/vnc_des_tool",
+                )
+                .arg(
+                    Arg::new("shell")
+                        .help("目标shell")
+                        .value_name("SHELL")
+                        .value_parser(clap::value_parser!(Shell))
+                        .required(true)
+                        .index(1),
                 ),
         )
 }
@@ -202,7 +496,16 @@ fn create_processor(matches: &ArgMatches) -> Result<VncDesProcessor, VncDesError
         if matches.get_flag("verbose") {
             println!("🔧 使用自定义密钥: {}", hex_key);
         }
-        return VncDesProcessor::with_hex_key(hex_key);
+        return VncDesProcessor::with_hex_key(hex_key).map(warn_weak_key);
+    }
+
+    // 检查是否从标准输入读取密钥
+    if matches.get_flag("key_stdin") {
+        let hex_key = read_key_line(io::stdin().lock())?;
+        if matches.get_flag("verbose") {
+            println!("🔧 使用标准输入提供的密钥");
+        }
+        return VncDesProcessor::with_hex_key(&hex_key).map(warn_weak_key);
     }
 
     // 检查是否指定了配置文件
@@ -214,6 +517,32 @@ fn create_processor(matches: &ArgMatches) -> Result<VncDesProcessor, VncDesError
         return Ok(VncDesProcessor::new(config));
     }
 
+    // 检查是否指定了环境变量；未指定任何密钥或产品选项时若设置了 VNC_DES_KEY 则自动使用
+    let key_env = matches.get_one::<String>("key_env");
+    let product = matches.get_one::<String>("product");
+    if key_env.is_some() || (product.is_none() && env::var_os(DEFAULT_KEY_ENV_VAR).is_some()) {
+        let provider = EnvKeyProvider::new(key_env.map_or(DEFAULT_KEY_ENV_VAR, String::as_str));
+        let key = provider.key()?;
+        if matches.get_flag("verbose") {
+            println!("🔧 使用环境变量 {} 提供的密钥", provider.var_name());
+        }
+        return Ok(warn_weak_key(VncDesProcessor::with_key(key)));
+    }
+
+    // 检查是否指定了VNC产品
+    if let Some(name) = product {
+        let product = VncProduct::from_name(name)
+            .ok_or_else(|| VncDesError::config_error(format!("未知的VNC产品: {}", name)))?;
+        if matches.get_flag("verbose") {
+            println!(
+                "🔧 使用 {} 默认密钥: {}",
+                product.name(),
+                hex::encode(product.default_key())
+            );
+        }
+        return Ok(VncDesProcessor::new(VncDesConfig::for_product(product)));
+    }
+
     // 使用默认配置
     if matches.get_flag("verbose") {
         println!("🔧 使用默认VNC密钥: {}", hex::encode(TIGHTVNC_DEFAULT_KEY));
@@ -221,21 +550,123 @@ fn create_processor(matches: &ArgMatches) -> Result<VncDesProcessor, VncDesError
     Ok(VncDesProcessor::default())
 }
 
+/// 手动输入的密钥为DES弱密钥或半弱密钥时向标准错误输出警告（不影响标准输出的结果）
+fn warn_weak_key(processor: VncDesProcessor) -> VncDesProcessor {
+    for warning in processor.config().warnings() {
+        if warning.is_weak_key() {
+            eprintln!("⚠️  警告: {}", warning);
+        }
+    }
+    processor
+}
+
+/// 从输入流读取首行作为16进制密钥
+fn read_key_line<R: BufRead>(mut reader: R) -> Result<String, VncDesError> {
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+
+    let hex_key = line.trim();
+    if hex_key.is_empty() {
+        return Err(VncDesError::invalid_key_format("标准输入中未读取到密钥"));
+    }
+
+    Ok(hex_key.to_string())
+}
+
+/// 获取明文密码：参数为 `-` 或指定 `--stdin` 时从标准输入读取
+///
+/// 标准输入为终端时关闭回显提示输入，否则读取首行（仅去除行尾换行符）
+fn read_password(matches: &ArgMatches, arg: Option<&String>) -> Result<String, VncDesError> {
+    match arg {
+        Some(password) if password != "-" => return Ok(password.clone()),
+        _ => {}
+    }
+
+    if matches.get_flag("key_stdin") {
+        return Err(VncDesError::config_error(
+            "--key-stdin 与从标准输入读取密码不能同时使用",
+        ));
+    }
+
+    if io::stdin().is_terminal() {
+        return Ok(rpassword::prompt_password("🔑 请输入密码: ")?);
+    }
+
+    read_password_line(io::stdin().lock())
+}
+
+/// 从输入流读取首行作为明文密码（保留首尾空格）
+fn read_password_line<R: BufRead>(mut reader: R) -> Result<String, VncDesError> {
+    let mut line = String::new();
+    if reader.read_line(&mut line)? == 0 {
+        return Err(VncDesError::invalid_password_format(
+            "标准输入中未读取到密码",
+        ));
+    }
+
+    let password = line.strip_suffix('\n').unwrap_or(&line);
+    let password = password.strip_suffix('\r').unwrap_or(password);
+    Ok(password.to_string())
+}
+
+/// 规范化16进制输入（去除 0x 前缀和分隔符，转为小写）
+fn normalize_hex(hex_password: &str) -> String {
+    VncDesProcessor::normalize_hex_string(hex_password)
+}
+
+/// 读取 `--format` 选择的密文编码
+fn selected_encoding(matches: &ArgMatches) -> Encoding {
+    match matches.get_one::<String>("format").map(String::as_str) {
+        Some("base64") => Encoding::Base64,
+        _ => Encoding::Hex,
+    }
+}
+
+/// 读取 `--hex-case` 选择的十六进制大小写
+fn selected_hex_case(matches: &ArgMatches) -> HexCase {
+    match matches.get_one::<String>("hex_case").map(String::as_str) {
+        Some("upper") => HexCase::Upper,
+        _ => HexCase::Lower,
+    }
+}
+
 /// 处理加密命令
 fn handle_encrypt(matches: &ArgMatches) -> Result<(), VncDesError> {
-    let password = matches.get_one::<String>("password").unwrap();
-    let quiet = matches.get_flag("quiet");
-    let verbose = matches.get_flag("verbose");
+    let passwords = match matches.get_many::<String>("password") {
+        Some(args) => {
+            // 标准输入只能读取一次，多个 `-` 会让后面的密码读到空输入
+            if args.clone().filter(|arg| *arg == "-").count() > 1 {
+                return Err(VncDesError::config_error(
+                    "只能有一个密码从标准输入（-）读取",
+                ));
+            }
+            args.map(|arg| read_password(matches, Some(arg)))
+                .collect::<Result<Vec<_>, _>>()?
+        }
+        None => vec![read_password(matches, None)?],
+    };
 
     let mut processor = create_processor(matches)?;
+    if matches.get_flag("no_min") {
+        let config = processor.config().clone().with_min_password_length(0);
+        processor.set_config(config);
+    }
+
+    if passwords.len() > 1 {
+        return encrypt_many(matches, &processor, &passwords);
+    }
+    let password = &passwords[0];
+    let quiet = matches.get_flag("quiet");
+    let verbose = matches.get_flag("verbose");
 
     // 加密密码
     let encrypted = processor.encrypt_password(password)?;
-    let hex_string = VncDesProcessor::to_hex_string(&encrypted);
+    let encoded = encode_encrypted(matches, &encrypted);
+    let encoding = selected_encoding(matches);
 
     if quiet {
         // 静默模式，仅输出结果
-        println!("{}", hex_string);
+        println!("{}", encoded);
     } else {
         // 详细模式，显示完整信息
         println!("🔐 VNC DES 密码加密");
@@ -246,10 +677,10 @@ fn handle_encrypt(matches: &ArgMatches) -> Result<(), VncDesError> {
         }
 
         println!("📝 原始密码: '{}'", password);
-        if password.len() > processor.config().max_password_length {
-            let truncated = &password[..processor.config().max_password_length];
+        let truncated = processor.config().truncate_password(password);
+        if truncated.len() < password.len() {
             println!(
-                "⚠️  警告: 密码长度超过{}字符，已截断为: '{}'",
+                "⚠️  警告: 密码长度超过{}字节，已截断为: '{}'",
                 processor.config().max_password_length,
                 truncated
             );
@@ -258,18 +689,17 @@ fn handle_encrypt(matches: &ArgMatches) -> Result<(), VncDesError> {
         if verbose {
             println!("🔒 加密字节: {:?}", encrypted);
         }
-        println!("🔤 十六进制: {}", hex_string);
+        match encoding {
+            Encoding::Hex => println!("🔤 十六进制: {}", encoded),
+            Encoding::Base64 => println!("🔤 Base64: {}", encoded),
+        }
         println!("✅ 加密完成");
 
         // 验证加密正确性
         if verbose {
             match processor.decrypt_password(&encrypted) {
                 Ok(decrypted) => {
-                    let expected = if password.len() > processor.config().max_password_length {
-                        &password[..processor.config().max_password_length]
-                    } else {
-                        password
-                    };
+                    let expected = processor.config().truncate_password(password);
 
                     if decrypted == expected {
                         println!("✅ 验证: 加密解密一致");
@@ -284,23 +714,133 @@ fn handle_encrypt(matches: &ArgMatches) -> Result<(), VncDesError> {
         }
     }
 
+    if matches.get_flag("qr") {
+        print_qr(&encrypted)?;
+    }
+
+    Ok(())
+}
+
+/// 按 `--format` 和 `--hex-case` 编码加密结果
+fn encode_encrypted(matches: &ArgMatches, encrypted: &[u8]) -> String {
+    match selected_encoding(matches) {
+        Encoding::Hex => selected_hex_case(matches).encode(encrypted),
+        encoding => encoding.encode(encrypted),
+    }
+}
+
+/// 加密多个密码，静默模式下每行输出一个密文，否则输出表格
+///
+/// 全部加密成功后才开始输出，任一密码无效时不产生部分结果
+fn encrypt_many(
+    matches: &ArgMatches,
+    processor: &VncDesProcessor,
+    passwords: &[String],
+) -> Result<(), VncDesError> {
+    if matches.get_flag("qr") {
+        return Err(VncDesError::config_error("--qr 只能用于单个密码"));
+    }
+
+    let encoded = passwords
+        .iter()
+        .map(|password| {
+            let encrypted = processor.encrypt_password(password)?;
+            Ok(encode_encrypted(matches, &encrypted))
+        })
+        .collect::<Result<Vec<_>, VncDesError>>()?;
+
+    if matches.get_flag("quiet") {
+        for line in &encoded {
+            println!("{}", line);
+        }
+        return Ok(());
+    }
+
+    println!("🔐 VNC DES 密码加密（{}个）", passwords.len());
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    if matches.get_flag("verbose") {
+        println!("🔧 使用密钥: {}", processor.config().key_as_hex());
+    }
+    let width = encoded.iter().map(String::len).max().unwrap_or(0);
+    for (i, (password, line)) in passwords.iter().zip(&encoded).enumerate() {
+        let note = if processor.config().truncate_password(password).len() < password.len() {
+            "  ⚠️ 已截断"
+        } else {
+            ""
+        };
+        println!(
+            "{:>3}  {:<width$}  '{}'{}",
+            i + 1,
+            line,
+            password,
+            note,
+            width = width
+        );
+    }
+    println!("✅ 加密完成");
+
+    Ok(())
+}
+
+/// 以终端二维码形式输出加密结果
+#[cfg(feature = "qrcode")]
+fn print_qr(encrypted: &[u8]) -> Result<(), VncDesError> {
+    println!("{}", vnc_des::to_qr_terminal(encrypted)?);
+    Ok(())
+}
+
+/// 以终端二维码形式输出加密结果（未启用 qrcode 特性）
+#[cfg(not(feature = "qrcode"))]
+fn print_qr(_encrypted: &[u8]) -> Result<(), VncDesError> {
+    Err(VncDesError::unsupported(
+        "二维码输出需要启用 qrcode 特性重新编译",
+    ))
+}
+
+/// 输出两个密钥的子密钥表差异
+#[cfg(feature = "debug-internals")]
+fn print_schedule_diff(key_a: &str, key_b: &str) -> Result<(), VncDesError> {
+    let key_a = VncDesConfig::new().with_hex_key(key_a)?.encryption_key;
+    let key_b = VncDesConfig::new().with_hex_key(key_b)?.encryption_key;
+    let schedule_a = vnc_des::VncDesEngine::dump_schedule(&key_a);
+    let schedule_b = vnc_des::VncDesEngine::dump_schedule(&key_b);
+    let differing = vnc_des::VncDesEngine::compare_schedules(&key_a, &key_b);
+
+    println!("🔍 子密钥表对比");
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    for &i in &differing {
+        println!(
+            "子密钥[{:2}]: {:08x} -> {:08x}",
+            i, schedule_a[i], schedule_b[i]
+        );
+    }
+    println!("差异子密钥: {}/{}", differing.len(), schedule_a.len());
     Ok(())
 }
 
+/// 输出两个密钥的子密钥表差异（未启用 debug-internals 特性）
+#[cfg(not(feature = "debug-internals"))]
+fn print_schedule_diff(_key_a: &str, _key_b: &str) -> Result<(), VncDesError> {
+    Err(VncDesError::unsupported(
+        "子密钥表对比需要启用 debug-internals 特性重新编译",
+    ))
+}
+
 /// 处理解密命令
 fn handle_decrypt(matches: &ArgMatches) -> Result<(), VncDesError> {
-    let hex_password = matches.get_one::<String>("hex_password").unwrap();
     let quiet = matches.get_flag("quiet");
     let verbose = matches.get_flag("verbose");
 
-    let mut processor = create_processor(matches)?;
+    let processor = create_processor(matches)?;
+
+    let encoding = selected_encoding(matches);
 
-    // 清理输入（移除空格，转为小写）
-    let clean_hex = hex_password
-        .chars()
-        .filter(|c| !c.is_whitespace())
-        .collect::<String>()
-        .to_lowercase();
+    // 从文件读取时，恰好8字节视为二进制passwd文件，否则按文本处理
+    let in_file = matches.get_one::<String>("in_file");
+    let input = match in_file {
+        Some(path) => read_encrypted_file(path)?,
+        None => EncryptedInput::Text(matches.get_one::<String>("hex_password").unwrap().clone()),
+    };
 
     if !quiet {
         println!("🔓 VNC DES 密码解密");
@@ -310,16 +850,64 @@ fn handle_decrypt(matches: &ArgMatches) -> Result<(), VncDesError> {
             println!("🔧 使用密钥: {}", processor.config().key_as_hex());
         }
 
-        println!("🔤 输入十六进制: {}", hex_password);
-        if clean_hex != *hex_password {
-            println!("🧹 清理后格式: {}", clean_hex);
+        if let Some(path) = in_file {
+            let kind = if matches!(input, EncryptedInput::Binary(_)) {
+                "二进制passwd文件"
+            } else {
+                "文本文件"
+            };
+            println!("📄 输入文件: {}（{}）", path, kind);
         }
     }
 
-    // 解析十六进制并解密
-    let encrypted = VncDesProcessor::from_hex_string(&clean_hex)?;
+    // 按输入编码解析并解密
+    let encrypted = match input {
+        EncryptedInput::Binary(bytes) => bytes,
+        EncryptedInput::Text(text) => {
+            // 清理输入（移除空格，转为小写）
+            let clean_hex = normalize_hex(&text);
+
+            if !quiet {
+                match encoding {
+                    Encoding::Hex => {
+                        println!("🔤 输入十六进制: {}", text);
+                        if clean_hex != text {
+                            println!("🧹 清理后格式: {}", clean_hex);
+                        }
+                    }
+                    Encoding::Base64 => println!("🔤 输入Base64: {}", text),
+                }
+            }
+
+            match encoding {
+                Encoding::Hex => VncDesProcessor::from_hex_string(&clean_hex)?,
+                Encoding::Base64 => VncDesProcessor::from_base64_string(text.trim())?,
+            }
+        }
+    };
     let decrypted = processor.decrypt_password(&encrypted)?;
 
+    if let Some(expected) = matches.get_one::<String>("compare") {
+        let is_match = if matches.get_flag("timing_safe") {
+            constant_time_eq(decrypted.as_bytes(), expected.as_bytes())
+        } else {
+            decrypted == *expected
+        };
+
+        if quiet {
+            println!("{}", is_match);
+        } else if is_match {
+            println!("✅ 比较结果: 解密结果与给定明文一致");
+        } else {
+            println!("❌ 比较结果: 解密结果与给定明文不一致");
+        }
+
+        if !is_match {
+            process::exit(EXIT_MISMATCH);
+        }
+        return Ok(());
+    }
+
     if quiet {
         // 静默模式，仅输出结果
         println!("{}", decrypted);
@@ -334,14 +922,63 @@ fn handle_decrypt(matches: &ArgMatches) -> Result<(), VncDesError> {
     Ok(())
 }
 
+/// 待解密的输入
+enum EncryptedInput {
+    /// 原始密文字节（二进制passwd文件）
+    Binary(Vec<u8>),
+    /// 按 `--format` 解析的文本
+    Text(String),
+}
+
+/// 读取 `decrypt --in-file` 指定的文件
+///
+/// 恰好8字节时视为 `vncpasswd` 生成的二进制文件（16进制和Base64文本都不可能恰好8字节），
+/// 否则必须是UTF-8文本，首尾空白被忽略
+fn read_encrypted_file(path: &str) -> Result<EncryptedInput, VncDesError> {
+    let content = std::fs::read(path)?;
+    if content.len() == PASSWD_BLOCK_SIZE {
+        return Ok(EncryptedInput::Binary(content));
+    }
+
+    let text = String::from_utf8(content).map_err(|_| {
+        VncDesError::invalid_password_format(format!(
+            "无法识别的文件内容：既不是{}字节的二进制passwd文件，也不是文本",
+            PASSWD_BLOCK_SIZE
+        ))
+    })?;
+    Ok(EncryptedInput::Text(text.trim().to_string()))
+}
+
 /// 处理验证命令
 fn handle_verify(matches: &ArgMatches) -> Result<(), VncDesError> {
-    let password = matches.get_one::<String>("password").unwrap();
-    let hex_password = matches.get_one::<String>("hex_password").unwrap();
+    if let Some(path) = matches.get_one::<String>("batch") {
+        return handle_verify_batch(matches, path);
+    }
+
+    // 使用 --stdin 时唯一的位置参数是加密密码
+    let (password, hex_password) = if matches.get_flag("stdin") {
+        let positional: Vec<&String> = ["password", "hex_password"]
+            .into_iter()
+            .filter_map(|id| matches.get_one::<String>(id))
+            .collect();
+        let [hex_password] = positional[..] else {
+            return Err(VncDesError::config_error(
+                "使用 --stdin 时只需提供16进制加密密码",
+            ));
+        };
+        (read_password(matches, None)?, hex_password)
+    } else {
+        let password = matches.get_one::<String>("password");
+        (
+            read_password(matches, password)?,
+            matches.get_one::<String>("hex_password").unwrap(),
+        )
+    };
+    let password = &password;
     let quiet = matches.get_flag("quiet");
     let verbose = matches.get_flag("verbose");
 
-    let mut processor = create_processor(matches)?;
+    let processor = create_processor(matches)?;
 
     if !quiet {
         println!("🔍 VNC DES 密码验证");
@@ -355,23 +992,15 @@ fn handle_verify(matches: &ArgMatches) -> Result<(), VncDesError> {
         println!("🔤 加密密码: {}", hex_password);
     }
 
-    // 解析十六进制
-    let clean_hex = hex_password
-        .chars()
-        .filter(|c| !c.is_whitespace())
-        .collect::<String>()
-        .to_lowercase();
-
-    let encrypted = VncDesProcessor::from_hex_string(&clean_hex)?;
-
-    // 验证密码
-    let is_match = processor.verify_password(password, &encrypted)?;
+    // 解析十六进制并验证密码（始终使用常量时间比较）
+    let clean_hex = normalize_hex(hex_password);
+    let is_match = processor.verify_hex(password, &clean_hex)?;
 
     if quiet {
         // 静默模式，输出布尔值
         println!("{}", is_match);
         if !is_match {
-            process::exit(1);
+            process::exit(EXIT_MISMATCH);
         }
     } else {
         if is_match {
@@ -387,13 +1016,151 @@ fn handle_verify(matches: &ArgMatches) -> Result<(), VncDesError> {
                 println!("🔍 预期加密结果: {}", clean_hex);
             }
 
-            process::exit(1);
+            process::exit(EXIT_MISMATCH);
+        }
+    }
+
+    Ok(())
+}
+
+/// 批量验证文件中的密码对
+///
+/// 每行格式为 `明文<TAB>16进制密码`，空行和以 `#` 开头的行被忽略。所有行共用一个处理器，
+/// 存在格式错误的行时以 [`EXIT_ERROR`] 退出，否则存在不匹配时以 [`EXIT_MISMATCH`] 退出。
+fn handle_verify_batch(matches: &ArgMatches, path: &str) -> Result<(), VncDesError> {
+    let content = std::fs::read_to_string(path)?;
+    let quiet = matches.get_flag("quiet");
+    let processor = create_processor(matches)?;
+
+    if !quiet {
+        println!("🔍 VNC DES 批量密码验证: {}", path);
+        println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    }
+
+    let (mut matched, mut mismatched, mut invalid) = (0usize, 0usize, 0usize);
+    for (index, line) in content.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.trim_end_matches('\r');
+        if line.trim().is_empty() || line.trim_start().starts_with('#') {
+            continue;
+        }
+
+        let result = match line.split_once('\t') {
+            Some((password, hex_password)) => {
+                processor.verify_hex(password, &normalize_hex(hex_password))
+            }
+            None => Err(VncDesError::invalid_password_format(
+                "缺少TAB分隔的16进制密码",
+            )),
+        };
+
+        match result {
+            Ok(true) => {
+                matched += 1;
+                if !quiet {
+                    println!("✅ 第{}行: 密码匹配", line_number);
+                }
+            }
+            Ok(false) => {
+                mismatched += 1;
+                if !quiet {
+                    println!("❌ 第{}行: 密码不匹配", line_number);
+                }
+            }
+            Err(e) => {
+                invalid += 1;
+                if !quiet {
+                    println!("⚠️  第{}行: {}", line_number, e);
+                }
+            }
         }
     }
 
+    if !quiet {
+        println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    }
+    let mut summary = format!("{} matched, {} mismatched", matched, mismatched);
+    if invalid > 0 {
+        summary.push_str(&format!(", {} invalid", invalid));
+    }
+    println!("{}", summary);
+
+    if invalid > 0 {
+        process::exit(EXIT_ERROR);
+    }
+    if mismatched > 0 {
+        process::exit(EXIT_MISMATCH);
+    }
+
     Ok(())
 }
 
+/// 处理识别VNC实现命令
+///
+/// 只根据解密结果是否为可打印ASCII判断，多个实现共用同一密钥时会同时列出
+fn handle_identify(matches: &ArgMatches) -> Result<(), VncDesError> {
+    let hex_password = matches.get_one::<String>("hex_password").unwrap();
+    let quiet = matches.get_flag("quiet");
+    let encrypted = VncDesProcessor::from_hex_string(&normalize_hex(hex_password))?;
+
+    if !quiet {
+        println!("🔎 VNC 实现识别");
+        println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+        println!("🔤 加密密码: {}", hex_password);
+    }
+
+    let mut found = false;
+    for product in KnownKey::ALL {
+        let processor = VncDesProcessor::with_key(product.key());
+        let decrypted = processor.decrypt_password_bytes(&encrypted)?;
+
+        let printable =
+            !decrypted.is_empty() && decrypted.iter().all(|&b| (0x20..0x7f).contains(&b));
+        if !printable {
+            continue;
+        }
+
+        found = true;
+        // 可打印ASCII一定是有效的UTF-8
+        let candidate = String::from_utf8_lossy(&decrypted);
+        if quiet {
+            println!("{}\t{}", product.name(), candidate);
+        } else {
+            println!(
+                "✅ {} ({}): '{}'",
+                product.name(),
+                hex::encode(product.key()),
+                candidate
+            );
+        }
+    }
+
+    if !found {
+        if !quiet {
+            println!("❌ 没有已知密钥能解密出可打印ASCII，密文可能使用了自定义密钥");
+        }
+        process::exit(EXIT_MISMATCH);
+    }
+
+    Ok(())
+}
+
+/// 处理十六进制校验命令
+fn handle_validate_hex(matches: &ArgMatches) -> Result<(), VncDesError> {
+    let hex_password = matches.get_one::<String>("hex_password").unwrap();
+    let clean_hex = normalize_hex(hex_password);
+
+    if VncDesProcessor::is_valid_hex(&clean_hex) {
+        println!("valid");
+        println!("{}", clean_hex);
+        Ok(())
+    } else {
+        println!("invalid");
+        println!("{}", clean_hex);
+        process::exit(EXIT_MISMATCH);
+    }
+}
+
 /// 处理演示命令
 fn handle_demo(matches: &ArgMatches) -> Result<(), VncDesError> {
     let password = matches
@@ -439,27 +1206,153 @@ fn handle_demo(matches: &ArgMatches) -> Result<(), VncDesError> {
     Ok(())
 }
 
+/// 处理测试向量导出命令
+fn handle_dump_vectors(matches: &ArgMatches) -> Result<(), VncDesError> {
+    let count = matches
+        .get_one::<u32>("count")
+        .map_or(DUMP_VECTOR_PASSWORDS.len(), |&count| count as usize);
+    let processor = create_processor(matches)?;
+
+    println!("# key: {}", processor.config().key_as_hex());
+    for i in 0..count {
+        let password = match DUMP_VECTOR_PASSWORDS.get(i) {
+            Some(password) => password.to_string(),
+            None => format!("pw{:06}", i),
+        };
+        let (plain, hex) = processor.generate_test_pair(&password)?;
+        println!("{}\t{}", plain, hex);
+    }
+
+    Ok(())
+}
+
+/// 处理补全脚本生成命令
+fn handle_completions(matches: &ArgMatches) -> Result<(), VncDesError> {
+    let shell = *matches.get_one::<Shell>("shell").unwrap();
+    let mut cli = build_cli();
+    let bin_name = cli.get_name().to_string();
+    clap_complete::generate(shell, &mut cli, bin_name, &mut io::stdout());
+    Ok(())
+}
+
+/// 当前构建启用的Cargo特性
+fn enabled_features() -> Vec<&'static str> {
+    [
+        ("serde", cfg!(feature = "serde")),
+        ("toml", cfg!(feature = "toml")),
+        ("qrcode", cfg!(feature = "qrcode")),
+        ("smallvec", cfg!(feature = "smallvec")),
+        ("fast-keyschedule", cfg!(feature = "fast-keyschedule")),
+        ("async", cfg!(feature = "async")),
+        ("zeroize", cfg!(feature = "zeroize")),
+        ("wasm", cfg!(feature = "wasm")),
+        ("ffi", cfg!(feature = "ffi")),
+        ("logging", cfg!(feature = "logging")),
+        ("python", cfg!(feature = "python")),
+        ("debug-internals", cfg!(feature = "debug-internals")),
+    ]
+    .into_iter()
+    .filter_map(|(name, enabled)| enabled.then_some(name))
+    .collect()
+}
+
+/// 处理诊断命令
+fn handle_diagnose(matches: &ArgMatches) -> Result<(), VncDesError> {
+    let password = matches.get_one::<String>("password").unwrap();
+    let processor = create_processor(matches)?;
+
+    let encrypted = processor.encrypt_password(password)?;
+    let hex_string = VncDesProcessor::to_hex_string(&encrypted);
+    let schedule_fingerprint = processor.key_schedule_fingerprint()?;
+
+    // 解密结果应为原密码（超长时为其截断前缀），且重新加密后与原密文一致
+    let round_trip = match processor.decrypt_password(&encrypted) {
+        Ok(decrypted) => {
+            !decrypted.is_empty()
+                && password.starts_with(&decrypted)
+                && processor.encrypt_password(&decrypted)? == encrypted
+        }
+        Err(_) => false,
+    };
+
+    let config = processor.config();
+    let features = enabled_features();
+
+    if matches.get_flag("json") {
+        let config_json: serde_json::Value = serde_json::from_str(&config.to_public_json()?)?;
+        let report = serde_json::json!({
+            "version": info(),
+            "features": features,
+            "config": config_json,
+            "key_fingerprint": config.key_fingerprint(),
+            "password_length": password.len(),
+            "encrypted_hex": hex_string,
+            "schedule_fingerprint": schedule_fingerprint,
+            "round_trip": round_trip,
+        });
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        println!("----- vnc_des diagnose -----");
+        println!("version: {}", info());
+        println!("features: {}", features.join(", "));
+        for (field, value) in config.field_values() {
+            println!("config.{}: {}", field, value);
+        }
+        println!("key_fingerprint: {}", config.key_fingerprint());
+        println!("password_length: {}", password.len());
+        println!("encrypted_hex: {}", hex_string);
+        println!("schedule_fingerprint: {}", schedule_fingerprint);
+        println!("round_trip: {}", if round_trip { "ok" } else { "failed" });
+        println!("----------------------------");
+    }
+
+    if !round_trip {
+        process::exit(EXIT_MISMATCH);
+    }
+
+    Ok(())
+}
+
 /// 处理配置命令
 fn handle_config(matches: &ArgMatches) -> Result<(), VncDesError> {
     if matches.get_flag("show") {
         // 显示当前配置
         let processor = create_processor(matches)?;
         let config = processor.config();
+        let redact = matches.get_flag("redact");
 
         println!("🔧 当前配置信息");
         println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-        println!("密钥 (16进制): {}", config.key_as_hex());
+        if redact {
+            println!("密钥指纹: {}", config.key_fingerprint());
+        } else {
+            println!("密钥 (16进制): {}", config.key_as_hex());
+        }
         println!("严格模式: {}", config.strict_mode);
         println!("自动截断: {}", config.auto_truncate);
         println!("最大密码长度: {}", config.max_password_length);
+        println!("最小密码长度: {}", config.min_password_length);
+        if !redact {
+            println!("校验和 (SHA-256): {}", config.checksum());
+        }
 
         println!();
         println!("配置JSON格式:");
-        println!("{}", config.to_json()?);
+        if redact {
+            println!("{}", config.to_public_json()?);
+        } else {
+            println!("{}", config.to_json()?);
+        }
 
         return Ok(());
     }
 
+    if matches.get_flag("security") {
+        let processor = create_processor(matches)?;
+        println!("{}", processor.security_report());
+        return Ok(());
+    }
+
     if let Some(file_path) = matches.get_one::<String>("generate") {
         // 生成配置文件
         let config = VncDesConfig::default();
@@ -482,21 +1375,62 @@ fn handle_config(matches: &ArgMatches) -> Result<(), VncDesError> {
                 println!("   严格模式: {}", config.strict_mode);
                 println!("   自动截断: {}", config.auto_truncate);
                 println!("   最大密码长度: {}", config.max_password_length);
+                println!("   最小密码长度: {}", config.min_password_length);
+                println!("   校验和: {}", config.checksum());
+
+                if let Some(expected) = matches.get_one::<String>("expect_checksum") {
+                    if config.verify_checksum(expected) {
+                        println!("✅ 校验和匹配");
+                    } else {
+                        println!("❌ 校验和不匹配，期望: {}", expected);
+                        process::exit(EXIT_MISMATCH);
+                    }
+                }
             }
             Err(e) => {
                 println!("❌ 配置文件无效: {}", e);
-                process::exit(1);
+                process::exit(EXIT_ERROR);
             }
         }
 
         return Ok(());
     }
 
+    if let Some(mut files) = matches.get_many::<String>("diff") {
+        // 对比两个配置文件
+        let (file_a, file_b) = (files.next().unwrap(), files.next().unwrap());
+        let config_a = VncDesConfig::from_file(file_a)?;
+        let config_b = VncDesConfig::from_file(file_b)?;
+        let diffs = config_a.diff(&config_b);
+
+        if diffs.is_empty() {
+            println!("✅ 配置一致: {} == {}", file_a, file_b);
+            return Ok(());
+        }
+
+        println!("🔍 配置差异: {} <-> {}", file_a, file_b);
+        println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+        for diff in &diffs {
+            println!("{}: {} -> {}", diff.field, diff.this, diff.other);
+        }
+        process::exit(EXIT_MISMATCH);
+    }
+
+    if let Some(mut keys) = matches.get_many::<String>("compare_schedule") {
+        let (key_a, key_b) = (keys.next().unwrap(), keys.next().unwrap());
+        return print_schedule_diff(key_a, key_b);
+    }
+
     // 如果没有指定任何选项，显示帮助
     println!("请使用以下选项之一:");
     println!("  --show           显示当前配置");
+    println!("  --show --redact  显示当前配置（隐藏密钥）");
+    println!("  --security       显示安全评估报告");
     println!("  --generate FILE  生成配置文件");
     println!("  --validate FILE  验证配置文件");
+    println!("  --validate FILE --expect-checksum SHA256  验证配置文件及校验和");
+    println!("  --diff FILE_A FILE_B  对比两个配置文件");
+    println!("  --compare-schedule KEY_A KEY_B  对比两个密钥的子密钥表");
 
     Ok(())
 }
@@ -521,4 +1455,77 @@ mod tests {
         let processor = create_processor(&matches).unwrap();
         assert_eq!(processor.config().encryption_key, TIGHTVNC_DEFAULT_KEY);
     }
+
+    #[test]
+    fn test_product_option() {
+        let matches = build_cli()
+            .try_get_matches_from(vec!["vnc_des_tool", "--product", "realvnc", "demo"])
+            .unwrap();
+        let processor = create_processor(&matches).unwrap();
+        assert_eq!(
+            processor.config().encryption_key,
+            VncProduct::RealVnc.default_key()
+        );
+
+        let result =
+            build_cli().try_get_matches_from(vec!["vnc_des_tool", "--product", "foo", "demo"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_vnc_flavor_alias() {
+        let matches = build_cli()
+            .try_get_matches_from(vec!["vnc_des_tool", "--vnc-flavor", "ultravnc", "demo"])
+            .unwrap();
+        let processor = create_processor(&matches).unwrap();
+        assert_eq!(
+            processor.config().encryption_key,
+            VncProduct::UltraVnc.key()
+        );
+
+        // --key 优先于 --vnc-flavor
+        let matches = build_cli()
+            .try_get_matches_from(vec![
+                "vnc_des_tool",
+                "--vnc-flavor",
+                "realvnc",
+                "--key",
+                "0123456789abcdef",
+                "demo",
+            ])
+            .unwrap();
+        let processor = create_processor(&matches).unwrap();
+        assert_eq!(processor.config().key_as_hex(), "0123456789abcdef");
+    }
+
+    #[test]
+    fn test_key_sources_conflict() {
+        let result = build_cli().try_get_matches_from(vec![
+            "vnc_des_tool",
+            "--key",
+            "0123456789abcdef",
+            "--key-stdin",
+            "demo",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_read_password_line() {
+        assert_eq!(
+            read_password_line(&b" pass word \r\n"[..]).unwrap(),
+            " pass word "
+        );
+        assert_eq!(read_password_line(&b"secret"[..]).unwrap(), "secret");
+        assert_eq!(read_password_line(&b"\n"[..]).unwrap(), "");
+        assert!(read_password_line(&b""[..]).is_err());
+    }
+
+    #[test]
+    fn test_read_key_line() {
+        let key = read_key_line(&b"  0123456789abcdef \nignored\n"[..]).unwrap();
+        assert_eq!(key, "0123456789abcdef");
+
+        assert!(read_key_line(&b"\n"[..]).is_err());
+    }
 }
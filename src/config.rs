@@ -2,44 +2,563 @@
 //!
 //! 提供VNC DES模块的配置管理功能，支持可配置的密钥和其他参数
 
+use crate::crypto::des::VncDesEngine;
+use crate::crypto::scrub::scrub;
 use crate::error::{Result, VncDesError};
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::borrow::Cow;
+use std::fmt;
+use std::str::FromStr;
 
 /// 常见VNC实现的默认硬编码密钥（如TightVNC）
 /// 来源：TightVNC源代码 util/VncPassCrypt.cpp:29
 /// 注意：这是VNC协议实现层面的约定，不是协议标准本身的一部分
 pub const TIGHTVNC_DEFAULT_KEY: [u8; 8] = [23, 82, 107, 6, 35, 78, 88, 7];
 
+/// RealVNC的默认硬编码密钥
+/// 来源：RealVNC/TigerVNC common/rfb/obfuscate.cxx（继承自原始vncauth.c的fixedkey）
+pub const REALVNC_DEFAULT_KEY: [u8; 8] = [23, 82, 107, 6, 35, 78, 88, 7];
+
+/// UltraVNC的默认硬编码密钥
+/// 来源：UltraVNC源代码 rfb/vncauth.c
+pub const ULTRAVNC_DEFAULT_KEY: [u8; 8] = [23, 82, 107, 6, 35, 78, 88, 7];
+
+/// VNC产品（实现）类型
+///
+/// 用于选择对应实现的默认密钥及产品特有的行为差异：
+///
+/// - `TightVnc`: 密码以8字节密文存储（注册表或 `~/.vnc/passwd`），使用标准固定密钥
+/// - `RealVnc`: 与TightVNC使用相同的固定密钥和单块格式；4.x及以后版本在注册表/配置中
+///   以16字符十六进制保存
+/// - `UltraVnc`: 同样使用固定密钥，但 `ultravnc.ini` 中的 `passwd` 字段通常为18个
+///   十六进制字符，末尾多出的1字节不属于密文，解密前需去除
+///
+/// 目前已知的三种实现使用相同的默认密钥，显式区分产品可以记录意图，
+/// 并为今后出现差异的实现预留扩展点。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VncProduct {
+    /// TightVNC
+    TightVnc,
+    /// RealVNC（包括衍生的TigerVNC）
+    RealVnc,
+    /// UltraVNC
+    UltraVnc,
+}
+
+/// 已知的VNC固定密钥
+///
+/// 与 [`VncProduct`] 是同一类型：每种VNC实现对应一个默认固定密钥，
+/// 通过 [`key`](VncProduct::key) 获取，通过 [`from_name`](VncProduct::from_name) 按名称解析
+pub type KnownKey = VncProduct;
+
+impl VncProduct {
+    /// 所有支持的产品
+    pub const ALL: [VncProduct; 3] = [Self::TightVnc, Self::RealVnc, Self::UltraVnc];
+
+    /// 获取产品的默认密钥
+    pub fn default_key(&self) -> [u8; 8] {
+        match self {
+            Self::TightVnc => TIGHTVNC_DEFAULT_KEY,
+            Self::RealVnc => REALVNC_DEFAULT_KEY,
+            Self::UltraVnc => ULTRAVNC_DEFAULT_KEY,
+        }
+    }
+
+    /// 获取产品的默认密钥（[`default_key`](Self::default_key) 的别名，供 [`KnownKey`] 使用）
+    pub fn key(&self) -> [u8; 8] {
+        self.default_key()
+    }
+
+    /// 获取产品名称（小写，用于命令行）
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::TightVnc => "tightvnc",
+            Self::RealVnc => "realvnc",
+            Self::UltraVnc => "ultravnc",
+        }
+    }
+
+    /// 根据名称解析产品（忽略大小写）
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|product| product.name().eq_ignore_ascii_case(name.trim()))
+    }
+}
+
+/// 将16字符的十六进制字符串解析为8字节密钥
+///
+/// 长度错误返回 [`VncDesError::InvalidHexLength`]，字符无效返回 [`VncDesError::HexDecodeError`]
+pub(crate) fn parse_hex_key(hex_key: &str) -> Result<[u8; 8]> {
+    let len = hex_key.chars().count();
+    if len != 16 {
+        return Err(VncDesError::invalid_hex_length(16, len));
+    }
+
+    let key_bytes = hex::decode(hex_key)
+        .map_err(|e| VncDesError::hex_decode_error(format!("无法解析十六进制密钥: {}", e)))?;
+
+    let mut key = [0u8; 8];
+    key.copy_from_slice(&key_bytes);
+    Ok(key)
+}
+
+/// 按字节去除填充后的有效长度（见 [`PasswordEncoding::unpadded_len_with`]）
+pub(crate) fn unpadded_byte_len(block: &[u8], pad_byte: u8) -> usize {
+    if pad_byte == 0 {
+        block.iter().position(|&b| b == 0).unwrap_or(block.len())
+    } else {
+        block
+            .iter()
+            .rposition(|&b| b != pad_byte)
+            .map_or(0, |last| last + 1)
+    }
+}
+
+/// 安全警告
+///
+/// 描述配置中存在的、不会导致操作失败但值得关注的安全问题
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum SecurityWarning {
+    /// 使用了公开的VNC默认密钥
+    DefaultKey,
+    /// 密钥全为0
+    AllZeroKey,
+    /// 密钥为DES弱密钥（全0密钥仅报告为 `AllZeroKey`）
+    WeakKey,
+    /// 密钥为DES半弱密钥
+    SemiWeakKey,
+}
+
+impl SecurityWarning {
+    /// 是否为DES弱密钥类问题（全0、弱密钥、半弱密钥），严格模式下这类问题会使验证失败
+    pub fn is_weak_key(&self) -> bool {
+        matches!(self, Self::AllZeroKey | Self::WeakKey | Self::SemiWeakKey)
+    }
+}
+
+impl fmt::Display for SecurityWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            Self::DefaultKey => "使用了公开的VNC默认密钥",
+            Self::AllZeroKey => "密钥全为0",
+            Self::WeakKey => "密钥为DES弱密钥",
+            Self::SemiWeakKey => "密钥为DES半弱密钥",
+        };
+        f.write_str(msg)
+    }
+}
+
+/// 密码编码方式
+///
+/// 决定明文密码在DES加密前如何转换为字节。编码后的字节被截断/填充为8字节，
+/// 因此 `Utf16Le`/`Utf16Be` 下最多只能容纳4个UTF-16码元。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum PasswordEncoding {
+    /// UTF-8（默认，ASCII密码与标准VNC实现一致）
+    #[default]
+    Utf8,
+    /// Latin-1（ISO-8859-1），仅支持 U+0000..=U+00FF 的字符
+    Latin1,
+    /// UTF-16小端序（部分Windows VNC服务器）
+    ///
+    /// VNC密码块只有8字节，因此最多只能容纳4个UTF-16码元，超出部分会被截断
+    Utf16Le,
+    /// UTF-16大端序（同样最多容纳4个UTF-16码元）
+    Utf16Be,
+}
+
+impl PasswordEncoding {
+    /// 所有支持的编码方式
+    pub const ALL: [PasswordEncoding; 4] = [
+        PasswordEncoding::Utf8,
+        PasswordEncoding::Latin1,
+        PasswordEncoding::Utf16Le,
+        PasswordEncoding::Utf16Be,
+    ];
+
+    /// 根据名称解析编码方式（忽略大小写）
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|encoding| encoding.name().eq_ignore_ascii_case(name.trim()))
+    }
+
+    /// 获取编码名称
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Utf8 => "utf8",
+            Self::Latin1 => "latin1",
+            Self::Utf16Le => "utf16_le",
+            Self::Utf16Be => "utf16_be",
+        }
+    }
+
+    /// 单个字符编码后的字节数
+    fn char_len(&self, c: char) -> usize {
+        match self {
+            Self::Utf8 => c.len_utf8(),
+            Self::Latin1 => 1,
+            Self::Utf16Le | Self::Utf16Be => c.len_utf16() * 2,
+        }
+    }
+
+    /// 密码编码后的字节数（不实际编码，Latin-1下无法编码的字符按1字节计）
+    pub fn encoded_len(&self, password: &str) -> usize {
+        match self {
+            Self::Utf8 => password.len(),
+            _ => password.chars().map(|c| self.char_len(c)).sum(),
+        }
+    }
+
+    /// 截断密码，使其编码后不超过 `max_bytes` 字节
+    ///
+    /// 只在字符边界截断：UTF-8下不会切开多字节字符，UTF-16下不会拆开代理对
+    ///
+    /// ```
+    /// use vnc_des::PasswordEncoding;
+    ///
+    /// assert_eq!(PasswordEncoding::Utf8.truncate("密码密", 8), "密码");
+    /// // 😀 需要一对代理码元（4字节），放不下时整个舍弃
+    /// assert_eq!(PasswordEncoding::Utf16Le.truncate("abc😀", 8), "abc");
+    /// ```
+    pub fn truncate<'a>(&self, password: &'a str, max_bytes: usize) -> &'a str {
+        let mut total = 0;
+        for (i, c) in password.char_indices() {
+            total += self.char_len(c);
+            if total > max_bytes {
+                return &password[..i];
+            }
+        }
+        password
+    }
+
+    /// 将密码编码为字节（UTF-8时直接借用，不分配内存）
+    pub fn encode<'a>(&self, password: &'a str) -> Result<Cow<'a, [u8]>> {
+        match self {
+            Self::Utf8 => Ok(Cow::Borrowed(password.as_bytes())),
+            Self::Latin1 => password
+                .chars()
+                .map(|c| {
+                    u8::try_from(u32::from(c)).map_err(|_| {
+                        VncDesError::invalid_password_format(format!(
+                            "字符 '{}' 无法以Latin-1编码",
+                            c
+                        ))
+                    })
+                })
+                .collect::<Result<Vec<u8>>>()
+                .map(Cow::Owned),
+            Self::Utf16Le => Ok(Cow::Owned(
+                password.encode_utf16().flat_map(u16::to_le_bytes).collect(),
+            )),
+            Self::Utf16Be => Ok(Cow::Owned(
+                password.encode_utf16().flat_map(u16::to_be_bytes).collect(),
+            )),
+        }
+    }
+
+    /// 去除填充后的有效字节数
+    ///
+    /// 第一个0字节（UTF-16下为第一个对齐的0码元）之后视为填充
+    pub fn unpadded_len(&self, block: &[u8]) -> usize {
+        self.unpadded_len_with(block, 0)
+    }
+
+    /// 按指定填充字节去除填充后的有效字节数
+    ///
+    /// 填充字节为0时与 [`unpadded_len`](Self::unpadded_len) 相同，第一个0字节之后都视为填充；
+    /// 其他填充字节（如空格）常出现在密码中间，因此只去除末尾连续的填充。
+    /// UTF-16下按码元处理，填充码元的两个字节都是填充字节。
+    pub fn unpadded_len_with(&self, block: &[u8], pad_byte: u8) -> usize {
+        match self {
+            Self::Utf8 | Self::Latin1 => unpadded_byte_len(block, pad_byte),
+            Self::Utf16Le | Self::Utf16Be => {
+                let pad = [pad_byte, pad_byte];
+                let mut units = block.chunks_exact(2);
+                if pad_byte == 0 {
+                    units
+                        .position(|pair| pair == pad)
+                        .map_or(block.len(), |units| units * 2)
+                } else {
+                    units
+                        .rposition(|pair| pair != pad)
+                        .map_or(0, |last| (last + 1) * 2)
+                }
+            }
+        }
+    }
+
+    /// 将解密得到的块解码为字符串（通常为8字节，多块密码时为8的倍数）
+    ///
+    /// 第一个0字节（UTF-16下为第一个0码元）之后视为填充
+    pub fn decode(&self, block: &[u8]) -> Result<String> {
+        self.decode_exact(&block[..self.unpadded_len(block)])
+    }
+
+    /// 将字节完整解码为字符串，不去除填充（0字节作为 `'\0'` 字符保留）
+    pub fn decode_exact(&self, bytes: &[u8]) -> Result<String> {
+        match self {
+            Self::Utf8 => std::str::from_utf8(bytes).map(str::to_string).map_err(|e| {
+                VncDesError::decryption_failed(format!("解密结果不是有效的{}: {}", self, e))
+            }),
+            Self::Latin1 => Ok(bytes.iter().map(|&b| char::from(b)).collect()),
+            Self::Utf16Le | Self::Utf16Be => {
+                if bytes.len() % 2 != 0 {
+                    return Err(VncDesError::decryption_failed(format!(
+                        "{}数据长度必须为偶数，实际长度: {}",
+                        self,
+                        bytes.len()
+                    )));
+                }
+
+                let units: Vec<u16> = bytes
+                    .chunks_exact(2)
+                    .map(|pair| {
+                        let pair = [pair[0], pair[1]];
+                        if *self == Self::Utf16Be {
+                            u16::from_be_bytes(pair)
+                        } else {
+                            u16::from_le_bytes(pair)
+                        }
+                    })
+                    .collect();
+
+                String::from_utf16(&units).map_err(|e| {
+                    VncDesError::decryption_failed(format!("解密结果不是有效的{}: {}", self, e))
+                })
+            }
+        }
+    }
+}
+
+impl fmt::Display for PasswordEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// DES密钥校验位处理方式
+///
+/// 标准DES密钥每个字节的最低位是奇校验位，不参与加密。VNC会反转每个密钥字节的位序，
+/// 因此在VNC密钥中校验位对应每个字节的最高位（`0x80`）。VNC本身忽略校验位，
+/// 该选项仅用于与严格检查校验位的工具互操作，不会改变加密结果。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum ParityMode {
+    /// 忽略校验位（默认，与VNC行为一致）
+    #[default]
+    Ignore,
+    /// 在使用前将每个字节调整为奇校验
+    SetOdd,
+    /// 校验位不正确时报错
+    Validate,
+}
+
+impl ParityMode {
+    /// 所有校验位处理方式
+    pub const ALL: [ParityMode; 3] = [ParityMode::Ignore, ParityMode::SetOdd, ParityMode::Validate];
+
+    /// 根据名称解析校验位处理方式（忽略大小写）
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|mode| mode.name().eq_ignore_ascii_case(name.trim()))
+    }
+
+    /// 获取模式名称
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Ignore => "ignore",
+            Self::SetOdd => "set_odd",
+            Self::Validate => "validate",
+        }
+    }
+
+    /// 按照该模式处理密钥
+    ///
+    /// 奇偶性与位序无关，因此直接统计每个字节中1的个数
+    pub fn apply(&self, key: [u8; 8]) -> Result<[u8; 8]> {
+        match self {
+            Self::Ignore => Ok(key),
+            Self::SetOdd => Ok(key.map(|byte| {
+                if byte.count_ones() % 2 == 0 {
+                    byte ^ 0x80
+                } else {
+                    byte
+                }
+            })),
+            Self::Validate => match key.iter().position(|byte| byte.count_ones() % 2 == 0) {
+                Some(index) => Err(VncDesError::invalid_key_format(format!(
+                    "密钥第 {} 个字节 0x{:02x} 不满足奇校验",
+                    index, key[index]
+                ))),
+                None => Ok(key),
+            },
+        }
+    }
+}
+
+impl fmt::Display for ParityMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// 配置字段差异
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldDiff {
+    /// 字段名
+    pub field: &'static str,
+    /// 当前配置中的值
+    pub this: String,
+    /// 对比配置中的值
+    pub other: String,
+}
+
 /// VNC DES配置
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct VncDesConfig {
     /// DES加密密钥（8字节）
+    ///
+    /// 序列化为16字符十六进制字符串，反序列化时同时接受旧版的字节数组形式
+    #[cfg_attr(feature = "serde", serde(with = "hex_key_serde"))]
     pub encryption_key: [u8; 8],
     /// 是否使用严格模式（严格验证密码长度等）
     pub strict_mode: bool,
     /// 是否自动截断超长密码
     pub auto_truncate: bool,
-    /// 最大密码长度
+    /// 最大密码长度（按 `password_encoding` 编码后的字节数）
     pub max_password_length: usize,
+    /// 最小密码长度（按 `password_encoding` 编码后的字节数）
+    #[cfg_attr(feature = "serde", serde(default = "default_min_password_length"))]
+    pub min_password_length: usize,
+    /// 密码编码方式
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub password_encoding: PasswordEncoding,
+    /// 是否严格遵循RFC 6143（见 [`with_rfc6143_strict`](Self::with_rfc6143_strict)）
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub rfc6143_strict: bool,
+    /// 密钥校验位处理方式
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub key_parity: ParityMode,
+    /// 是否拒绝DES弱密钥和半弱密钥
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub reject_weak_keys: bool,
+    /// 短密码的填充字节（默认0，与标准VNC实现一致）
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub pad_byte: u8,
+}
+
+/// `encryption_key` 的序列化格式
+///
+/// 写出时使用十六进制字符串（`"17526b06234e5807"`），便于手工编辑；
+/// 读取时也接受早期版本写出的字节数组（`[23, 82, 107, ...]`）
+#[cfg(feature = "serde")]
+mod hex_key_serde {
+    use super::parse_hex_key;
+    use serde::de::{self, SeqAccess, Visitor};
+    use serde::{Deserializer, Serializer};
+    use std::fmt;
+
+    pub fn serialize<S: Serializer>(key: &[u8; 8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&hex::encode(key))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[u8; 8], D::Error> {
+        deserializer.deserialize_any(KeyVisitor)
+    }
+
+    struct KeyVisitor;
+
+    impl<'de> Visitor<'de> for KeyVisitor {
+        type Value = [u8; 8];
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("16字符十六进制字符串或8个字节的数组")
+        }
+
+        fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+            parse_hex_key(value.trim()).map_err(E::custom)
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let mut key = [0u8; 8];
+            for (i, byte) in key.iter_mut().enumerate() {
+                *byte = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+            }
+            if seq.next_element::<u8>()?.is_some() {
+                return Err(de::Error::invalid_length(9, &self));
+            }
+            Ok(key)
+        }
+    }
+}
+
+const fn default_min_password_length() -> usize {
+    1
 }
 
 impl Default for VncDesConfig {
     fn default() -> Self {
-        Self {
-            encryption_key: TIGHTVNC_DEFAULT_KEY,
-            strict_mode: false,
-            auto_truncate: true,
-            max_password_length: 8,
-        }
+        Self::DEFAULT
+    }
+}
+
+impl Drop for VncDesConfig {
+    /// 销毁时清零密钥
+    fn drop(&mut self) {
+        scrub(&mut self.encryption_key);
     }
 }
 
 impl VncDesConfig {
+    /// 默认配置（TightVNC默认密钥），可用于 `const`/`static` 上下文
+    pub const DEFAULT: Self = Self::const_new(TIGHTVNC_DEFAULT_KEY);
+
     /// 创建一个新的配置
     pub fn new() -> Self {
         Self::default()
     }
 
+    /// 在编译期使用指定密钥创建配置，其余字段取默认值
+    ///
+    /// ```
+    /// use vnc_des::VncDesConfig;
+    ///
+    /// const CONFIG: VncDesConfig = VncDesConfig::const_new([1, 2, 3, 4, 5, 6, 7, 8]);
+    /// assert_eq!(CONFIG.encryption_key, [1, 2, 3, 4, 5, 6, 7, 8]);
+    /// ```
+    pub const fn const_new(key: [u8; 8]) -> Self {
+        Self {
+            encryption_key: key,
+            strict_mode: false,
+            auto_truncate: true,
+            max_password_length: 8,
+            min_password_length: default_min_password_length(),
+            password_encoding: PasswordEncoding::Utf8,
+            rfc6143_strict: false,
+            key_parity: ParityMode::Ignore,
+            reject_weak_keys: false,
+            pad_byte: 0,
+        }
+    }
+
+    /// 使用指定VNC产品的默认密钥创建配置
+    pub fn for_product(product: VncProduct) -> Self {
+        Self::default().with_key(product.default_key())
+    }
+
     /// 设置加密密钥
     pub fn with_key(mut self, key: [u8; 8]) -> Self {
         self.encryption_key = key;
@@ -48,23 +567,82 @@ impl VncDesConfig {
 
     /// 从十六进制字符串设置密钥
     pub fn with_hex_key(mut self, hex_key: &str) -> Result<Self> {
-        let key_bytes = hex::decode(hex_key)
-            .map_err(|e| VncDesError::hex_decode_error(format!("无法解析十六进制密钥: {}", e)))?;
+        self.encryption_key = parse_hex_key(hex_key)?;
+        Ok(self)
+    }
 
-        if key_bytes.len() != 8 {
-            return Err(VncDesError::invalid_key_format(format!(
-                "密钥长度必须为8字节，实际长度: {}",
-                key_bytes.len()
-            )));
+    /// 将任意长度的字节折叠为8字节密钥并设置
+    ///
+    /// 折叠算法：密钥初始为全0，输入的第 `i` 个字节异或到密钥的第 `i % 8` 个字节。
+    /// 输入恰好为8字节时结果与 [`with_key`](Self::with_key) 相同，不足8字节时其余字节保持为0。
+    ///
+    /// 这不是VNC标准的一部分，只是为持有超长密钥（例如16字节）的用户提供一个可复现的映射，
+    /// 并不会增加任何安全性——折叠后的密钥强度仍然只有DES的56位。
+    pub fn with_folded_key(self, bytes: &[u8]) -> Self {
+        let mut key = [0u8; 8];
+        for (i, byte) in bytes.iter().enumerate() {
+            key[i % 8] ^= byte;
         }
+        self.with_key(key)
+    }
 
-        let mut key = [0u8; 8];
-        key.copy_from_slice(&key_bytes);
-        self.encryption_key = key;
-        Ok(self)
+    /// 设置RFC 6143严格合规模式
+    ///
+    /// 启用后会拒绝任何偏离规范的配置或操作：
+    ///
+    /// - 最大密码长度超过8字节（[`validate`](Self::validate) 报错）
+    /// - 非UTF-8的密码编码（[`validate`](Self::validate) 报错）
+    /// - 密钥不是已知VNC产品的标准密钥（[`validate`](Self::validate) 报错）
+    /// - 填充字节不为0（[`validate`](Self::validate) 报错）
+    /// - 包含非ASCII字符的密码（加密时报错）
+    ///
+    /// 挑战-响应接口以 `[u8; 16]` 接收挑战，长度由类型保证；通用的
+    /// `encrypt_blocks`/`decrypt_blocks` 不受此限制。处理器在创建或更新配置时验证一次，
+    /// 配置未通过验证时加密、解密等所有使用密钥的操作都会返回相应错误
+    pub fn with_rfc6143_strict(mut self, strict: bool) -> Self {
+        self.rfc6143_strict = strict;
+        self
+    }
+
+    /// 设置密钥校验位处理方式（见 [`ParityMode`]）
+    pub fn with_key_parity(mut self, mode: ParityMode) -> Self {
+        self.key_parity = mode;
+        self
+    }
+
+    /// 设置是否拒绝DES弱密钥和半弱密钥
+    ///
+    /// 启用后 [`validate`](Self::validate) 对这类密钥报错，处理器也会在加解密时拒绝
+    /// （包括来自密钥提供者的密钥）
+    pub fn with_reject_weak_keys(mut self, reject: bool) -> Self {
+        self.reject_weak_keys = reject;
+        self
+    }
+
+    /// 设置短密码的填充字节
+    ///
+    /// 标准VNC实现用0将密码补足8字节，个别服务器使用空格等其他字节。
+    /// 解密时按同一字节去除填充（见 [`PasswordEncoding::unpadded_len_with`]），
+    /// 因此加解密双方必须使用相同的配置
+    ///
+    /// ```
+    /// use vnc_des::{VncDesConfig, VncDesProcessor};
+    ///
+    /// let processor = VncDesProcessor::new(VncDesConfig::new().with_pad_byte(b' '));
+    /// let encrypted = processor.encrypt_password("a b")?;
+    /// assert_ne!(encrypted, VncDesProcessor::default().encrypt_password("a b")?);
+    /// assert_eq!(processor.decrypt_password(&encrypted)?, "a b");
+    /// # Ok::<(), vnc_des::VncDesError>(())
+    /// ```
+    pub fn with_pad_byte(mut self, pad_byte: u8) -> Self {
+        self.pad_byte = pad_byte;
+        self
     }
 
     /// 设置严格模式
+    ///
+    /// 严格模式隐含 [`with_reject_weak_keys`](Self::with_reject_weak_keys)：
+    /// 处理器在加解密时同样拒绝DES弱密钥和半弱密钥
     pub fn with_strict_mode(mut self, strict: bool) -> Self {
         self.strict_mode = strict;
         self
@@ -82,7 +660,50 @@ impl VncDesConfig {
         self
     }
 
+    /// 设置最小密码长度
+    pub fn with_min_password_length(mut self, length: usize) -> Self {
+        self.min_password_length = length;
+        self
+    }
+
+    /// 设置密码编码方式
+    pub fn with_password_encoding(mut self, encoding: PasswordEncoding) -> Self {
+        self.password_encoding = encoding;
+        self
+    }
+
+    /// 按当前配置检查实际使用的密钥
+    ///
+    /// 严格模式或 `reject_weak_keys` 下拒绝弱密钥和半弱密钥，RFC 6143严格模式下只接受
+    /// 标准VNC产品密钥。[`validate`](Self::validate) 用它检查 `encryption_key`，
+    /// 处理器用它检查密钥提供者返回的密钥
+    pub(crate) fn check_key(&self, key: &[u8; 8]) -> Result<()> {
+        if self.reject_weak_keys || self.strict_mode {
+            if VncDesEngine::is_weak_key(key) {
+                return Err(VncDesError::invalid_key_format("密钥为DES弱密钥"));
+            }
+            if VncDesEngine::is_semiweak_key(key) {
+                return Err(VncDesError::invalid_key_format("密钥为DES半弱密钥"));
+            }
+        }
+
+        if self.rfc6143_strict
+            && !VncProduct::ALL
+                .iter()
+                .any(|product| product.default_key() == *key)
+        {
+            return Err(VncDesError::config_error(
+                "RFC 6143严格模式下只能使用标准VNC产品密钥",
+            ));
+        }
+
+        Ok(())
+    }
+
     /// 验证配置
+    ///
+    /// 启用 `reject_weak_keys` 或严格模式时，DES弱密钥和半弱密钥（见 [`warnings`](Self::warnings)）
+    /// 返回 [`VncDesError::InvalidKeyFormat`]；否则只作为警告，不影响验证结果
     pub fn validate(&self) -> Result<()> {
         if self.max_password_length == 0 {
             return Err(VncDesError::config_error("最大密码长度不能为0"));
@@ -92,14 +713,259 @@ impl VncDesConfig {
             return Err(VncDesError::config_error("最大密码长度不能超过256"));
         }
 
+        if self.min_password_length > self.max_password_length {
+            return Err(VncDesError::config_error(format!(
+                "最小密码长度 {} 不能大于最大密码长度 {}",
+                self.min_password_length, self.max_password_length
+            )));
+        }
+
+        self.key_parity.apply(self.encryption_key)?;
+        self.check_key(&self.encryption_key)?;
+
+        if self.rfc6143_strict {
+            if self.max_password_length > 8 {
+                return Err(VncDesError::config_error(format!(
+                    "RFC 6143严格模式下最大密码长度不能超过8，当前: {}",
+                    self.max_password_length
+                )));
+            }
+
+            if self.password_encoding != PasswordEncoding::Utf8 {
+                return Err(VncDesError::config_error(format!(
+                    "RFC 6143严格模式下不支持 {} 密码编码",
+                    self.password_encoding
+                )));
+            }
+
+            if self.pad_byte != 0 {
+                return Err(VncDesError::config_error(format!(
+                    "RFC 6143严格模式下填充字节必须为0，当前: 0x{:02x}",
+                    self.pad_byte
+                )));
+            }
+        }
+
         Ok(())
     }
 
+    /// 检查配置中不会导致操作失败、但值得关注的安全问题
+    ///
+    /// 包括使用公开的默认密钥，以及全0密钥、4个DES弱密钥和12个半弱密钥（6对）。
+    /// 严格模式下弱密钥类问题由 [`validate`](Self::validate) 作为错误返回
+    ///
+    /// ```
+    /// use vnc_des::{SecurityWarning, VncDesConfig};
+    ///
+    /// let config = VncDesConfig::new().with_hex_key("0000000000000000")?;
+    /// assert_eq!(config.warnings(), [SecurityWarning::AllZeroKey]);
+    /// assert!(config.validate().is_ok());
+    /// assert!(config.with_strict_mode(true).validate().is_err());
+    /// # Ok::<(), vnc_des::VncDesError>(())
+    /// ```
+    pub fn warnings(&self) -> Vec<SecurityWarning> {
+        let mut warnings = Vec::new();
+
+        if VncProduct::ALL
+            .iter()
+            .any(|product| product.default_key() == self.encryption_key)
+        {
+            warnings.push(SecurityWarning::DefaultKey);
+        }
+
+        if self.encryption_key == [0u8; 8] {
+            warnings.push(SecurityWarning::AllZeroKey);
+        } else if VncDesEngine::is_weak_key(&self.encryption_key) {
+            warnings.push(SecurityWarning::WeakKey);
+        } else if VncDesEngine::is_semiweak_key(&self.encryption_key) {
+            warnings.push(SecurityWarning::SemiWeakKey);
+        }
+
+        warnings
+    }
+
+    /// 将密码截断到编码后 `max_password_length` 字节以内
+    ///
+    /// 长度按配置的 [`PasswordEncoding`] 编码后的字节数计算，截断位置向前调整到字符边界：
+    /// 多字节字符不会被切开，UTF-16下代理对也不会被拆开，因此结果可能短于最大长度
+    ///
+    /// ```
+    /// use vnc_des::{PasswordEncoding, VncDesConfig};
+    ///
+    /// let config = VncDesConfig::new();
+    /// assert_eq!(config.truncate_password("password123"), "password");
+    /// // 每个汉字3字节，第3个字会跨过第8字节，整个字被舍弃
+    /// assert_eq!(config.truncate_password("密码密码密"), "密码");
+    ///
+    /// // UTF-16下每个汉字2字节
+    /// let config = config.with_password_encoding(PasswordEncoding::Utf16Le);
+    /// assert_eq!(config.truncate_password("密码密码密"), "密码密码");
+    /// ```
+    pub fn truncate_password<'a>(&self, password: &'a str) -> &'a str {
+        self.password_encoding
+            .truncate(password, self.max_password_length)
+    }
+
     /// 获取密钥的十六进制表示
     pub fn key_as_hex(&self) -> String {
         hex::encode(self.encryption_key)
     }
 
+    /// 获取密钥指纹（SHA-256前16个十六进制字符）
+    ///
+    /// 用于在日志、差异对比等场景标识密钥，而不暴露原始密钥字节
+    pub fn key_fingerprint(&self) -> String {
+        hex::encode(&Sha256::digest(self.encryption_key)[..8])
+    }
+
+    /// 获取所有配置字段的名称及说明
+    ///
+    /// 顺序与 [`field_values`](Self::field_values) 一致，便于GUI/TUI工具通用地渲染设置表单
+    pub fn field_descriptions() -> &'static [(&'static str, &'static str)] {
+        &[
+            ("encryption_key", "DES加密密钥（8字节）"),
+            ("strict_mode", "严格模式：密码超长时报错而不是截断"),
+            ("auto_truncate", "非严格模式下自动截断超长密码"),
+            ("max_password_length", "最大密码长度（字节）"),
+            ("min_password_length", "最小密码长度（字节）"),
+            ("password_encoding", "密码编码方式"),
+            ("rfc6143_strict", "RFC 6143严格合规模式"),
+            ("key_parity", "密钥校验位处理方式"),
+            ("reject_weak_keys", "拒绝DES弱密钥和半弱密钥"),
+            ("pad_byte", "短密码的填充字节"),
+        ]
+    }
+
+    /// 获取所有配置字段的当前值（字符串形式）
+    ///
+    /// 密钥以指纹形式呈现，不会暴露原始密钥
+    pub fn field_values(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("encryption_key", self.key_fingerprint()),
+            ("strict_mode", self.strict_mode.to_string()),
+            ("auto_truncate", self.auto_truncate.to_string()),
+            ("max_password_length", self.max_password_length.to_string()),
+            ("min_password_length", self.min_password_length.to_string()),
+            ("password_encoding", self.password_encoding.to_string()),
+            ("rfc6143_strict", self.rfc6143_strict.to_string()),
+            ("key_parity", self.key_parity.to_string()),
+            ("reject_weak_keys", self.reject_weak_keys.to_string()),
+            ("pad_byte", format!("0x{:02x}", self.pad_byte)),
+        ]
+    }
+
+    /// 逐字段对比两个配置，返回存在差异的字段
+    ///
+    /// 密钥以指纹形式呈现
+    pub fn diff(&self, other: &Self) -> Vec<FieldDiff> {
+        self.field_values()
+            .into_iter()
+            .zip(other.field_values())
+            .filter(|((_, this), (_, other))| this != other)
+            .map(|((field, this), (_, other))| FieldDiff { field, this, other })
+            .collect()
+    }
+}
+
+/// 单行摘要格式
+///
+/// 形如 `key=17526b06234e5807 strict=false truncate=true maxlen=8 minlen=1 encoding=utf8
+/// rfc6143=false parity=ignore reject_weak=false pad=0x00`，包含全部字段，可由 [`FromStr`] 无损解析。
+/// 注意输出中包含明文密钥，写入日志前请确认密钥不属于敏感信息
+impl fmt::Display for VncDesConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "key={} strict={} truncate={} maxlen={} minlen={} encoding={} rfc6143={} parity={} reject_weak={} pad=0x{:02x}",
+            self.key_as_hex(),
+            self.strict_mode,
+            self.auto_truncate,
+            self.max_password_length,
+            self.min_password_length,
+            self.password_encoding,
+            self.rfc6143_strict,
+            self.key_parity,
+            self.reject_weak_keys,
+            self.pad_byte,
+        )
+    }
+}
+
+/// 解析 [`Display`](fmt::Display) 输出的单行格式
+///
+/// 字段之间以空白或 `;` 分隔，顺序任意；未出现的字段使用默认值。解析后会执行 [`validate`](VncDesConfig::validate)
+///
+/// ```
+/// use vnc_des::VncDesConfig;
+///
+/// let config: VncDesConfig = "key=0123456789abcdef;strict=true".parse()?;
+/// assert!(config.strict_mode);
+/// assert_eq!(config.to_string().parse::<VncDesConfig>()?.key_as_hex(), "0123456789abcdef");
+/// # Ok::<(), vnc_des::VncDesError>(())
+/// ```
+impl FromStr for VncDesConfig {
+    type Err = VncDesError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        fn parse_value<T: FromStr>(field: &str, value: &str) -> Result<T> {
+            value.parse().map_err(|_| {
+                VncDesError::config_error(format!("字段 {} 的值无效: {}", field, value))
+            })
+        }
+
+        let mut config = Self::default();
+        for pair in s
+            .split(|c: char| c == ';' || c.is_whitespace())
+            .filter(|pair| !pair.is_empty())
+        {
+            let (field, value) = pair
+                .split_once('=')
+                .ok_or_else(|| VncDesError::config_error(format!("缺少 '=': {}", pair)))?;
+
+            match field {
+                "key" => config.encryption_key = parse_hex_key(value)?,
+                "strict" => config.strict_mode = parse_value(field, value)?,
+                "truncate" => config.auto_truncate = parse_value(field, value)?,
+                "maxlen" => config.max_password_length = parse_value(field, value)?,
+                "minlen" => config.min_password_length = parse_value(field, value)?,
+                "encoding" => {
+                    config.password_encoding =
+                        PasswordEncoding::from_name(value).ok_or_else(|| {
+                            VncDesError::config_error(format!("未知的密码编码: {}", value))
+                        })?
+                }
+                "rfc6143" => config.rfc6143_strict = parse_value(field, value)?,
+                "parity" => {
+                    config.key_parity = ParityMode::from_name(value).ok_or_else(|| {
+                        VncDesError::config_error(format!("未知的校验位处理方式: {}", value))
+                    })?
+                }
+                "reject_weak" => config.reject_weak_keys = parse_value(field, value)?,
+                "pad" => {
+                    config.pad_byte = value
+                        .strip_prefix("0x")
+                        .map_or_else(|| value.parse(), |hex| u8::from_str_radix(hex, 16))
+                        .map_err(|_| {
+                            VncDesError::config_error(format!("字段 {} 的值无效: {}", field, value))
+                        })?
+                }
+                _ => {
+                    return Err(VncDesError::config_error(format!(
+                        "未知的配置字段: {}",
+                        field
+                    )));
+                }
+            }
+        }
+
+        config.validate()?;
+        Ok(config)
+    }
+}
+
+/// 序列化相关功能（需要启用 `serde` 特性）
+#[cfg(feature = "serde")]
+impl VncDesConfig {
     /// 从JSON字符串加载配置
     pub fn from_json(json: &str) -> Result<Self> {
         let config: Self = serde_json::from_str(json)?;
@@ -112,24 +978,274 @@ impl VncDesConfig {
         Ok(serde_json::to_string_pretty(self)?)
     }
 
+    /// 转换为可公开分享的JSON字符串（不含密钥）
+    ///
+    /// `encryption_key` 被替换为密钥指纹，并附加 `"key_redacted": true` 标记，
+    /// 适用于技术支持、遥测等需要分享实际配置的场景。结果无法再反序列化为配置。
+    pub fn to_public_json(&self) -> Result<String> {
+        let mut value = serde_json::to_value(self)?;
+        if let Some(object) = value.as_object_mut() {
+            object.insert(
+                "encryption_key".to_string(),
+                serde_json::Value::String(self.key_fingerprint()),
+            );
+            object.insert("key_redacted".to_string(), serde_json::Value::Bool(true));
+        }
+        Ok(serde_json::to_string_pretty(&value)?)
+    }
+
     /// 从文件加载配置
+    ///
+    /// 格式由扩展名决定（见 [`ConfigFormat::from_path`]），不支持的扩展名在读取文件前
+    /// 即返回错误；解析失败时错误信息中包含检测到的格式名称
     pub fn from_file<P: AsRef<std::path::Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let format = ConfigFormat::from_path(path)?;
         let content = std::fs::read_to_string(path)?;
-        Self::from_json(&content)
+        let result = match format {
+            ConfigFormat::Json => Self::from_json(&content),
+            ConfigFormat::Toml => Self::from_toml_str(&content),
+        };
+        // TOML的解析错误已带有格式名称，只需为JSON的解析错误补上；验证错误原样返回
+        result.map_err(|e| match e {
+            VncDesError::SerializationError(e) => {
+                VncDesError::config_error(format!("无法解析{}配置: {}", format.name(), e))
+            }
+            e => e,
+        })
     }
 
     /// 保存配置到文件
+    ///
+    /// 格式由扩展名决定（见 [`ConfigFormat::from_path`]）
     pub fn save_to_file<P: AsRef<std::path::Path>>(&self, path: P) -> Result<()> {
-        let json = self.to_json()?;
-        std::fs::write(path, json)?;
+        let content = self.to_file_content(path.as_ref())?;
+        std::fs::write(path, content)?;
         Ok(())
     }
+
+    /// 按文件扩展名序列化配置
+    fn to_file_content(&self, path: &std::path::Path) -> Result<String> {
+        match ConfigFormat::from_path(path)? {
+            ConfigFormat::Json => self.to_json(),
+            ConfigFormat::Toml => self.to_toml_string(),
+        }
+    }
+
+    #[cfg(feature = "toml")]
+    fn from_toml_str(toml: &str) -> Result<Self> {
+        Self::from_toml(toml)
+    }
+
+    #[cfg(not(feature = "toml"))]
+    fn from_toml_str(_toml: &str) -> Result<Self> {
+        Err(VncDesError::unsupported(format!(
+            "读取{}配置文件需要启用 toml 特性",
+            ConfigFormat::Toml.name()
+        )))
+    }
+
+    #[cfg(feature = "toml")]
+    fn to_toml_string(&self) -> Result<String> {
+        self.to_toml()
+    }
+
+    #[cfg(not(feature = "toml"))]
+    fn to_toml_string(&self) -> Result<String> {
+        Err(VncDesError::unsupported(format!(
+            "写入{}配置文件需要启用 toml 特性",
+            ConfigFormat::Toml.name()
+        )))
+    }
+
+    /// 加载配置文件，不存在时以默认配置创建
+    ///
+    /// 文件已存在时直接加载；否则先将默认配置完整写入同一目录下的临时文件，再以硬链接
+    /// 原子地放到目标路径，其他进程不会读到写了一半的文件，写入失败也不会留下残缺的配置。
+    /// 如果在检查与创建之间文件被其他进程创建，则改为加载该文件。
+    ///
+    /// 文件系统不支持硬链接（如FAT/exFAT、部分网络文件系统）时，改为以 `create_new`
+    /// 直接创建目标文件，写入失败时删除该文件。
+    pub fn load_or_create<P: AsRef<std::path::Path>>(path: P) -> Result<Self> {
+        use std::io::{ErrorKind, Write};
+
+        let path = path.as_ref();
+        if path.exists() {
+            return Self::from_file(path);
+        }
+
+        let config = Self::default();
+        let content = config.to_file_content(path)?;
+
+        let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+        let temp_path = path.with_file_name(format!(".{}.{}.tmp", file_name, std::process::id()));
+        let written = std::fs::File::create(&temp_path)
+            .and_then(|mut file| {
+                file.write_all(content.as_bytes())?;
+                file.sync_all()
+            })
+            .and_then(|()| match std::fs::hard_link(&temp_path, path) {
+                // 文件系统不支持硬链接
+                Err(e)
+                    if matches!(
+                        e.kind(),
+                        ErrorKind::Unsupported | ErrorKind::PermissionDenied
+                    ) =>
+                {
+                    Self::create_new_file(path, &content)
+                }
+                linked => linked,
+            });
+        let _ = std::fs::remove_file(&temp_path);
+
+        match written {
+            Ok(()) => Ok(config),
+            // 其他进程已抢先创建
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => Self::from_file(path),
+            Err(e) => Err(VncDesError::config_error(format!(
+                "无法创建配置文件 {}: {}",
+                path.display(),
+                e
+            ))),
+        }
+    }
+
+    /// 以 `create_new` 直接创建目标文件并写入内容，写入失败时删除已创建的文件
+    fn create_new_file(path: &std::path::Path, content: &str) -> std::io::Result<()> {
+        use std::io::Write;
+
+        let mut file = std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(path)?;
+        let written = file
+            .write_all(content.as_bytes())
+            .and_then(|_| file.sync_all());
+        if written.is_err() {
+            drop(file);
+            let _ = std::fs::remove_file(path);
+        }
+        written
+    }
+
+    /// 计算配置校验和（SHA-256十六进制）
+    ///
+    /// 校验和基于紧凑JSON规范形式计算，与文件中的格式化、空白无关，
+    /// 可用于检测分发的配置文件是否被篡改或损坏。
+    ///
+    /// 密钥以十六进制字符串参与计算；密钥仍以字节数组序列化的早期版本算出的校验和不再适用
+    pub fn checksum(&self) -> String {
+        // 结构体字段均为基础类型，序列化不会失败
+        let canonical = serde_json::to_string(self).unwrap_or_default();
+        hex::encode(Sha256::digest(canonical.as_bytes()))
+    }
+
+    /// 校验配置是否与期望的校验和一致（忽略大小写和首尾空白）
+    pub fn verify_checksum(&self, expected: &str) -> bool {
+        self.checksum().eq_ignore_ascii_case(expected.trim())
+    }
+}
+
+/// TOML格式支持（需要启用 `toml` 特性）
+#[cfg(feature = "toml")]
+impl VncDesConfig {
+    /// 从TOML字符串加载配置
+    pub fn from_toml(toml: &str) -> Result<Self> {
+        let config: Self = toml::from_str(toml).map_err(|e| {
+            VncDesError::config_error(format!("无法解析{}配置: {}", ConfigFormat::Toml.name(), e))
+        })?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// 转换为TOML字符串
+    pub fn to_toml(&self) -> Result<String> {
+        toml::to_string_pretty(self).map_err(|e| {
+            VncDesError::config_error(format!(
+                "无法序列化{}配置: {}",
+                ConfigFormat::Toml.name(),
+                e
+            ))
+        })
+    }
+}
+
+/// 配置文件格式
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    /// JSON格式
+    Json,
+    /// TOML格式（读写需要启用 `toml` 特性）
+    Toml,
+}
+
+#[cfg(feature = "serde")]
+impl ConfigFormat {
+    /// 根据文件扩展名检测配置格式（不区分大小写）
+    ///
+    /// `.json` 和没有扩展名的文件视为JSON，`.toml` 视为TOML，其他扩展名返回配置错误：
+    ///
+    /// ```
+    /// use vnc_des::ConfigFormat;
+    ///
+    /// assert_eq!(ConfigFormat::from_path("vnc.TOML".as_ref())?, ConfigFormat::Toml);
+    /// assert_eq!(ConfigFormat::from_path("vnc_config".as_ref())?, ConfigFormat::Json);
+    /// assert!(ConfigFormat::from_path("vnc.yaml".as_ref()).is_err());
+    /// # Ok::<(), vnc_des::VncDesError>(())
+    /// ```
+    pub fn from_path(path: &std::path::Path) -> Result<Self> {
+        let Some(ext) = path.extension() else {
+            return Ok(Self::Json);
+        };
+        if ext.eq_ignore_ascii_case("json") {
+            Ok(Self::Json)
+        } else if ext.eq_ignore_ascii_case("toml") {
+            Ok(Self::Toml)
+        } else {
+            Err(VncDesError::config_error(format!(
+                "不支持的配置文件格式: .{}（仅支持 .json 和 .toml）",
+                ext.to_string_lossy()
+            )))
+        }
+    }
+
+    /// 获取格式名称
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Json => "JSON",
+            Self::Toml => "TOML",
+        }
+    }
 }
 
 /// 配置构建器
+///
+/// 所有设置方法都可以直接链式调用，错误统一由 [`build`](Self::build) 返回：
+///
+/// ```
+/// use vnc_des::{VncDesConfigBuilder, VncDesError};
+///
+/// let result = VncDesConfigBuilder::new()
+///     .hex_key("not-a-key")
+///     .strict_mode(true)
+///     .build();
+/// assert!(matches!(result, Err(VncDesError::InvalidHexLength { .. })));
+/// ```
 #[derive(Debug, Default)]
 pub struct VncDesConfigBuilder {
     config: VncDesConfig,
+    /// 设置过程中遇到的第一个错误，由 `build` 返回
+    error: Option<VncDesError>,
+}
+
+impl From<VncDesConfig> for VncDesConfigBuilder {
+    fn from(config: VncDesConfig) -> Self {
+        Self {
+            config,
+            error: None,
+        }
+    }
 }
 
 impl VncDesConfigBuilder {
@@ -145,23 +1261,31 @@ impl VncDesConfigBuilder {
     }
 
     /// 从十六进制字符串设置密钥
-    pub fn hex_key(mut self, hex_key: &str) -> Result<Self> {
-        let key_bytes = hex::decode(hex_key)
-            .map_err(|e| VncDesError::hex_decode_error(format!("无法解析十六进制密钥: {}", e)))?;
-
-        if key_bytes.len() != 8 {
-            return Err(VncDesError::invalid_key_format(format!(
-                "密钥长度必须为8字节，实际长度: {}",
-                key_bytes.len()
-            )));
+    ///
+    /// 解析失败时不中断链式调用，错误保留到 [`build`](Self::build) 返回
+    /// （之后再设置密钥也不会清除该错误）。需要立即得到错误时使用 [`try_hex_key`](Self::try_hex_key)
+    pub fn hex_key(mut self, hex_key: &str) -> Self {
+        match parse_hex_key(hex_key) {
+            Ok(key) => self.config.encryption_key = key,
+            Err(e) => {
+                self.error.get_or_insert(e);
+            }
         }
+        self
+    }
 
-        let mut key = [0u8; 8];
-        key.copy_from_slice(&key_bytes);
-        self.config.encryption_key = key;
+    /// 从十六进制字符串设置密钥，解析失败时立即返回错误
+    pub fn try_hex_key(mut self, hex_key: &str) -> Result<Self> {
+        self.config.encryption_key = parse_hex_key(hex_key)?;
         Ok(self)
     }
 
+    /// 将任意长度的字节折叠为8字节密钥（见 [`VncDesConfig::with_folded_key`]）
+    pub fn folded_key(mut self, bytes: &[u8]) -> Self {
+        self.config = self.config.with_folded_key(bytes);
+        self
+    }
+
     /// 设置严格模式
     pub fn strict_mode(mut self, strict: bool) -> Self {
         self.config.strict_mode = strict;
@@ -180,8 +1304,50 @@ impl VncDesConfigBuilder {
         self
     }
 
+    /// 设置最小密码长度
+    pub fn min_password_length(mut self, length: usize) -> Self {
+        self.config.min_password_length = length;
+        self
+    }
+
+    /// 设置密码编码方式
+    pub fn password_encoding(mut self, encoding: PasswordEncoding) -> Self {
+        self.config.password_encoding = encoding;
+        self
+    }
+
+    /// 设置RFC 6143严格合规模式
+    pub fn rfc6143_strict(mut self, strict: bool) -> Self {
+        self.config.rfc6143_strict = strict;
+        self
+    }
+
+    /// 设置密钥校验位处理方式
+    pub fn key_parity(mut self, mode: ParityMode) -> Self {
+        self.config.key_parity = mode;
+        self
+    }
+
+    /// 设置是否拒绝DES弱密钥和半弱密钥
+    pub fn reject_weak_keys(mut self, reject: bool) -> Self {
+        self.config.reject_weak_keys = reject;
+        self
+    }
+
+    /// 设置短密码的填充字节（见 [`VncDesConfig::with_pad_byte`]）
+    pub fn pad_byte(mut self, pad_byte: u8) -> Self {
+        self.config.pad_byte = pad_byte;
+        self
+    }
+
     /// 构建配置
+    ///
+    /// 先返回设置过程中遇到的第一个错误（如 [`hex_key`](Self::hex_key) 的解析错误），
+    /// 再执行 [`VncDesConfig::validate`]
     pub fn build(self) -> Result<VncDesConfig> {
+        if let Some(e) = self.error {
+            return Err(e);
+        }
         self.config.validate()?;
         Ok(self.config)
     }
@@ -198,6 +1364,88 @@ mod tests {
         assert!(!config.strict_mode);
         assert!(config.auto_truncate);
         assert_eq!(config.max_password_length, 8);
+        assert_eq!(config.min_password_length, 1);
+    }
+
+    #[test]
+    fn test_min_password_length_validation() {
+        let result = VncDesConfigBuilder::new()
+            .min_password_length(9)
+            .max_password_length(8)
+            .build();
+        assert!(matches!(result, Err(VncDesError::ConfigError(_))));
+
+        let config = VncDesConfigBuilder::new()
+            .min_password_length(8)
+            .max_password_length(8)
+            .build()
+            .unwrap();
+        assert_eq!(config.min_password_length, 8);
+    }
+
+    #[test]
+    fn test_password_encoding_encode() {
+        assert_eq!(*PasswordEncoding::Utf8.encode("é").unwrap(), [0xC3, 0xA9]);
+        assert_eq!(*PasswordEncoding::Latin1.encode("é").unwrap(), [0xE9]);
+        assert!(PasswordEncoding::Latin1.encode("密").is_err());
+        assert_eq!(
+            *PasswordEncoding::Utf16Le.encode("ab").unwrap(),
+            [0x61, 0x00, 0x62, 0x00]
+        );
+        assert_eq!(
+            *PasswordEncoding::Utf16Be.encode("ab").unwrap(),
+            [0x00, 0x61, 0x00, 0x62]
+        );
+    }
+
+    #[test]
+    fn test_password_encoding_decode() {
+        let block = *b"caf\xe9\0\0\0\0";
+        assert_eq!(PasswordEncoding::Latin1.decode(&block).unwrap(), "café");
+
+        let err = PasswordEncoding::Utf8.decode(&block).unwrap_err();
+        assert!(err.to_string().contains("utf8"));
+
+        let block = [0x00, 0xD8, 0, 0, 0, 0, 0, 0];
+        let err = PasswordEncoding::Utf16Le.decode(&block).unwrap_err();
+        assert!(err.to_string().contains("utf16_le"));
+    }
+
+    #[test]
+    fn test_unpadded_len_with() {
+        // 0填充：第一个0之后都是填充
+        assert_eq!(
+            PasswordEncoding::Utf8.unpadded_len_with(b"ab\0c\0\0\0\0", 0),
+            2
+        );
+        // 其他填充字节只去除末尾，密码中间的同值字节保留
+        assert_eq!(
+            PasswordEncoding::Utf8.unpadded_len_with(b"a b     ", b' '),
+            3
+        );
+        assert_eq!(
+            PasswordEncoding::Latin1.unpadded_len_with(b"        ", b' '),
+            0
+        );
+        assert_eq!(
+            PasswordEncoding::Utf8.unpadded_len_with(b"12345678", b' '),
+            8
+        );
+
+        let block = [b'a', 0, b' ', 0, b' ', b' ', b' ', b' '];
+        assert_eq!(PasswordEncoding::Utf16Le.unpadded_len_with(&block, b' '), 4);
+        assert_eq!(PasswordEncoding::Utf16Le.unpadded_len_with(&block, 0), 8);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_min_password_length_default_from_json() {
+        // 旧版本配置文件中没有该字段时使用默认值
+        let json = r#"{"encryption_key":[23,82,107,6,35,78,88,7],"strict_mode":false,"auto_truncate":true,"max_password_length":8}"#;
+        let config = VncDesConfig::from_json(json).unwrap();
+        assert_eq!(config.encryption_key, TIGHTVNC_DEFAULT_KEY);
+        assert_eq!(config.min_password_length, 1);
+        assert_eq!(config.password_encoding, PasswordEncoding::Utf8);
     }
 
     #[test]
@@ -214,6 +1462,35 @@ mod tests {
         assert_eq!(config.max_password_length, 16);
     }
 
+    #[test]
+    fn test_builder_deferred_hex_key_error() {
+        let config = VncDesConfigBuilder::new()
+            .hex_key("0123456789abcdef")
+            .strict_mode(true)
+            .build()
+            .unwrap();
+        assert_eq!(config.key_as_hex(), "0123456789abcdef");
+
+        // 错误保留到build，且不会被后续的密钥设置覆盖
+        let result = VncDesConfigBuilder::new()
+            .hex_key("0123")
+            .hex_key("17526b06234e58zz")
+            .encryption_key(TIGHTVNC_DEFAULT_KEY)
+            .build();
+        assert!(matches!(
+            result,
+            Err(VncDesError::InvalidHexLength {
+                expected: 16,
+                actual: 4
+            })
+        ));
+
+        assert!(matches!(
+            VncDesConfigBuilder::new().try_hex_key("17526b06234e58zz"),
+            Err(VncDesError::HexDecodeError(_))
+        ));
+    }
+
     #[test]
     fn test_hex_key() {
         let hex_key = "17526b06234e5807";
@@ -221,15 +1498,537 @@ mod tests {
 
         assert_eq!(config.encryption_key, TIGHTVNC_DEFAULT_KEY);
         assert_eq!(config.key_as_hex(), hex_key);
+
+        // 长度错误与字符错误使用不同的错误变体
+        assert!(matches!(
+            VncDesConfig::new().with_hex_key("17526b06234e58"),
+            Err(VncDesError::InvalidHexLength {
+                expected: 16,
+                actual: 14
+            })
+        ));
+        assert!(matches!(
+            VncDesConfig::new().with_hex_key("17526b06234e58zz"),
+            Err(VncDesError::HexDecodeError(_))
+        ));
+    }
+
+    #[test]
+    fn test_field_descriptions() {
+        let config = VncDesConfig::default();
+
+        // 穷尽解构：新增字段时此处会编译失败，提醒同步更新字段元数据
+        let VncDesConfig {
+            encryption_key: _,
+            strict_mode: _,
+            auto_truncate: _,
+            max_password_length: _,
+            min_password_length: _,
+            password_encoding: _,
+            rfc6143_strict: _,
+            key_parity: _,
+            reject_weak_keys: _,
+            pad_byte: _,
+        } = config;
+        let field_count = 10;
+
+        let descriptions = VncDesConfig::field_descriptions();
+        let values = config.field_values();
+        assert_eq!(descriptions.len(), field_count);
+        assert_eq!(values.len(), field_count);
+        for ((name, description), (value_name, _)) in descriptions.iter().zip(&values) {
+            assert_eq!(name, value_name);
+            assert!(!description.is_empty());
+        }
+
+        assert_eq!(values[0].1, config.key_fingerprint());
+        assert!(!values[0].1.contains(&config.key_as_hex()));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_field_descriptions_match_serialized_fields() {
+        let json: serde_json::Value =
+            serde_json::from_str(&VncDesConfig::default().to_json().unwrap()).unwrap();
+        let keys: Vec<&str> = json
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(|k| k.as_str())
+            .collect();
+
+        assert_eq!(keys.len(), VncDesConfig::field_descriptions().len());
+        for (name, _) in VncDesConfig::field_descriptions() {
+            assert!(keys.contains(name), "缺少字段 {}", name);
+        }
+    }
+
+    #[test]
+    fn test_const_new() {
+        const CONFIG: VncDesConfig = VncDesConfig::const_new(REALVNC_DEFAULT_KEY);
+        static DEFAULT: VncDesConfig = VncDesConfig::DEFAULT;
+
+        assert_eq!(CONFIG.encryption_key, REALVNC_DEFAULT_KEY);
+        assert!(CONFIG
+            .diff(&VncDesConfig::default().with_key(REALVNC_DEFAULT_KEY))
+            .is_empty());
+        assert!(DEFAULT.diff(&VncDesConfig::default()).is_empty());
     }
 
+    #[test]
+    fn test_rfc6143_strict_validation() {
+        let strict = VncDesConfig::new().with_rfc6143_strict(true);
+        assert!(strict.validate().is_ok());
+        assert!(VncDesConfig::for_product(VncProduct::RealVnc)
+            .with_rfc6143_strict(true)
+            .validate()
+            .is_ok());
+
+        // 超过8字节的最大长度
+        let config = strict.clone().with_max_password_length(16);
+        assert!(config.validate().is_err());
+        assert!(config.with_rfc6143_strict(false).validate().is_ok());
+
+        // 非UTF-8编码
+        for encoding in [
+            PasswordEncoding::Latin1,
+            PasswordEncoding::Utf16Le,
+            PasswordEncoding::Utf16Be,
+        ] {
+            let config = strict.clone().with_password_encoding(encoding);
+            assert!(config.validate().is_err());
+        }
+
+        // 非0填充字节
+        let config = strict.clone().with_pad_byte(b' ');
+        assert!(config.validate().is_err());
+        assert!(config.with_rfc6143_strict(false).validate().is_ok());
+
+        // 非标准密钥
+        let config = strict.with_key([1, 2, 3, 4, 5, 6, 7, 8]);
+        assert!(config.validate().is_err());
+        assert!(config.with_rfc6143_strict(false).validate().is_ok());
+    }
+
+    #[test]
+    fn test_key_parity() {
+        // TightVNC默认密钥中 0x17、0x06、0x4e 为偶校验
+        let key = TIGHTVNC_DEFAULT_KEY;
+        let odd = [0x97, 0x52, 0x6b, 0x86, 0x23, 0xce, 0x58, 0x07];
+
+        assert_eq!(ParityMode::Ignore.apply(key).unwrap(), key);
+        assert_eq!(ParityMode::SetOdd.apply(key).unwrap(), odd);
+        assert_eq!(ParityMode::SetOdd.apply(odd).unwrap(), odd);
+        assert!(ParityMode::Validate.apply(key).is_err());
+        assert_eq!(ParityMode::Validate.apply(odd).unwrap(), odd);
+
+        let config = VncDesConfig::new().with_key_parity(ParityMode::Validate);
+        assert!(config.validate().is_err());
+        assert!(config.with_key(odd).validate().is_ok());
+    }
+
+    #[test]
+    fn test_drop_clears_key() {
+        let mut config = std::mem::ManuallyDrop::new(VncDesConfig::new());
+        assert_eq!(config.encryption_key, TIGHTVNC_DEFAULT_KEY);
+
+        // SAFETY: 只运行Drop，不释放内存；之后仅读取 Copy 字段检查清零结果
+        unsafe { std::mem::ManuallyDrop::drop(&mut config) };
+        assert_eq!(config.encryption_key, [0u8; 8]);
+    }
+
+    #[test]
+    fn test_reject_weak_keys() {
+        // 标准弱密钥 0101010101010101 与半弱密钥 01FE01FE01FE01FE 的VNC位序形式
+        let weak = [0x80; 8];
+        let semiweak = [0x80, 0x7F, 0x80, 0x7F, 0x80, 0x7F, 0x80, 0x7F];
+
+        assert!(VncDesConfig::new().with_key(weak).validate().is_ok());
+        assert_eq!(
+            VncDesConfig::new().with_key(semiweak).warnings(),
+            vec![SecurityWarning::SemiWeakKey]
+        );
+
+        let config = VncDesConfig::new().with_reject_weak_keys(true);
+        assert!(config.validate().is_ok());
+        assert!(config.clone().with_key(weak).validate().is_err());
+        assert!(config.with_key(semiweak).validate().is_err());
+
+        // 严格模式下弱密钥同样是错误，默认密钥只是警告
+        let strict = VncDesConfig::new().with_strict_mode(true);
+        assert!(strict.validate().is_ok());
+        assert_eq!(strict.warnings(), vec![SecurityWarning::DefaultKey]);
+        assert!(!SecurityWarning::DefaultKey.is_weak_key());
+        for key in [weak, semiweak, [0u8; 8]] {
+            let config = strict.clone().with_key(key);
+            assert!(config.warnings().iter().all(SecurityWarning::is_weak_key));
+            assert!(matches!(
+                config.validate(),
+                Err(VncDesError::InvalidKeyFormat(_))
+            ));
+        }
+        assert!(VncDesConfigBuilder::new()
+            .encryption_key(weak)
+            .reject_weak_keys(true)
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_folded_key() {
+        // 8字节输入保持不变
+        let config = VncDesConfig::new().with_folded_key(&TIGHTVNC_DEFAULT_KEY);
+        assert_eq!(config.encryption_key, TIGHTVNC_DEFAULT_KEY);
+
+        let bytes16: Vec<u8> = (0u8..16).collect();
+        let folded = VncDesConfig::new().with_folded_key(&bytes16).encryption_key;
+        assert_eq!(folded, [8, 8, 8, 8, 8, 8, 8, 8]);
+        assert_eq!(
+            VncDesConfig::new().with_folded_key(&bytes16).encryption_key,
+            folded
+        );
+
+        let bytes24: Vec<u8> = (0u8..24).collect();
+        let folded = VncDesConfig::new().with_folded_key(&bytes24).encryption_key;
+        assert_eq!(folded, [24, 25, 26, 27, 28, 29, 30, 31]);
+
+        // 不足8字节时其余位置为0
+        let folded = VncDesConfig::new()
+            .with_folded_key(&[0xAA, 0xBB])
+            .encryption_key;
+        assert_eq!(folded, [0xAA, 0xBB, 0, 0, 0, 0, 0, 0]);
+    }
+
+    #[cfg(feature = "serde")]
     #[test]
     fn test_json_serialization() {
         let config = VncDesConfig::default();
         let json = config.to_json().unwrap();
         let deserialized = VncDesConfig::from_json(&json).unwrap();
 
-        assert_eq!(config.encryption_key, deserialized.encryption_key);
-        assert_eq!(config.strict_mode, deserialized.strict_mode);
+        assert_eq!(config, deserialized);
+        assert_ne!(config, deserialized.with_strict_mode(true));
+    }
+
+    #[test]
+    fn test_product_names() {
+        for product in VncProduct::ALL {
+            assert_eq!(VncProduct::from_name(product.name()), Some(product));
+        }
+        assert_eq!(VncProduct::from_name("RealVNC"), Some(VncProduct::RealVnc));
+        assert_eq!(VncProduct::from_name("unknown"), None);
+    }
+
+    #[test]
+    fn test_for_product_canonical_password() {
+        use crate::crypto::VncDesProcessor;
+
+        // 各产品的默认密钥对 "test" 的加密结果
+        let cases = [
+            (VncProduct::TightVnc, "2f981dc548e09ec2"),
+            (VncProduct::RealVnc, "2f981dc548e09ec2"),
+            (VncProduct::UltraVnc, "2f981dc548e09ec2"),
+        ];
+
+        for (product, expected) in cases {
+            let config = VncDesConfig::for_product(product);
+            assert_eq!(config.encryption_key, product.default_key());
+            assert_eq!(
+                KnownKey::from_name(product.name()).unwrap().key(),
+                product.default_key()
+            );
+
+            let processor = VncDesProcessor::new(config);
+            let encrypted = processor.encrypt_password("test").unwrap();
+            assert_eq!(VncDesProcessor::to_hex_string(&encrypted), expected);
+        }
+    }
+
+    #[test]
+    fn test_diff() {
+        let a = VncDesConfig::default();
+        assert!(a.diff(&a.clone()).is_empty());
+
+        let b = VncDesConfig::default()
+            .with_key([1, 2, 3, 4, 5, 6, 7, 8])
+            .with_max_password_length(16);
+        let diffs = a.diff(&b);
+
+        assert_eq!(diffs.len(), 2);
+        assert_eq!(diffs[0].field, "encryption_key");
+        assert_eq!(diffs[0].this, a.key_fingerprint());
+        assert_eq!(diffs[0].other, b.key_fingerprint());
+        assert_ne!(diffs[0].other, b.key_as_hex());
+        assert_eq!(
+            diffs[1],
+            FieldDiff {
+                field: "max_password_length",
+                this: "8".to_string(),
+                other: "16".to_string(),
+            }
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_load_or_create() {
+        let path = std::env::temp_dir().join(format!(
+            "vnc_des_load_or_create_{}.json",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+
+        // 文件不存在：创建默认配置
+        let created = VncDesConfig::load_or_create(&path).unwrap();
+        assert_eq!(created.encryption_key, TIGHTVNC_DEFAULT_KEY);
+        assert_eq!(VncDesConfig::from_file(&path).unwrap(), created);
+        // 临时文件已清理
+        let temp_prefix = format!(".{}.", path.file_name().unwrap().to_string_lossy());
+        assert!(!std::fs::read_dir(path.parent().unwrap())
+            .unwrap()
+            .any(|entry| entry
+                .unwrap()
+                .file_name()
+                .to_string_lossy()
+                .starts_with(&temp_prefix)));
+
+        // 文件已存在：加载而不覆盖
+        VncDesConfig::new()
+            .with_strict_mode(true)
+            .save_to_file(&path)
+            .unwrap();
+        let loaded = VncDesConfig::load_or_create(&path).unwrap();
+        assert!(loaded.strict_mode);
+
+        std::fs::remove_file(&path).unwrap();
+
+        // 父目录不存在：返回清晰的错误
+        let missing_dir = std::env::temp_dir()
+            .join("vnc_des_no_such_dir")
+            .join("config.json");
+        let err = VncDesConfig::load_or_create(&missing_dir).unwrap_err();
+        assert!(err.to_string().contains("无法创建配置文件"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_create_new_file_fallback() {
+        // 不支持硬链接时使用的回退路径：只创建新文件，已存在时报告 AlreadyExists
+        let path =
+            std::env::temp_dir().join(format!("vnc_des_create_new_{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let content = VncDesConfig::default().to_json().unwrap();
+        VncDesConfig::create_new_file(&path, &content).unwrap();
+        assert_eq!(
+            VncDesConfig::from_file(&path).unwrap(),
+            VncDesConfig::default()
+        );
+
+        let err = VncDesConfig::create_new_file(&path, "{}").unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::AlreadyExists);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), content);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_toml_round_trip() {
+        let config = VncDesConfig::new()
+            .with_hex_key("0123456789abcdef")
+            .unwrap()
+            .with_strict_mode(true)
+            .with_key_parity(ParityMode::Validate);
+
+        let toml = config.to_toml().unwrap();
+        assert!(toml.contains("strict_mode = true"));
+        assert!(toml.contains(r#"encryption_key = "0123456789abcdef""#));
+        let parsed = VncDesConfig::from_toml(&toml).unwrap();
+        assert_eq!(parsed, config);
+
+        // 按扩展名选择格式
+        let path = std::env::temp_dir().join(format!("vnc_des_config_{}.TOML", std::process::id()));
+        config.save_to_file(&path).unwrap();
+        assert!(std::fs::read_to_string(&path)
+            .unwrap()
+            .contains("strict_mode = true"));
+        let loaded = VncDesConfig::from_file(&path).unwrap();
+        assert_eq!(loaded, config);
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(VncDesConfig::from_toml("strict_mode = 1").is_err());
+    }
+
+    #[cfg(all(feature = "serde", not(feature = "toml")))]
+    #[test]
+    fn test_toml_requires_feature() {
+        let path = std::env::temp_dir().join(format!("vnc_des_config_{}.toml", std::process::id()));
+        let err = VncDesConfig::default().save_to_file(&path).unwrap_err();
+        assert!(matches!(err, VncDesError::Unsupported(_)));
+        assert!(!path.exists());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_from_file_format_errors() {
+        let dir = std::env::temp_dir();
+        let id = std::process::id();
+
+        // 不支持的扩展名：读取文件前即报错
+        let yaml = dir.join(format!("vnc_des_config_{}.yaml", id));
+        let err = VncDesConfig::from_file(&yaml).unwrap_err();
+        assert!(matches!(err, VncDesError::ConfigError(_)));
+        assert!(err.to_string().contains(".yaml"));
+        assert!(VncDesConfig::default().save_to_file(&yaml).is_err());
+        assert!(!yaml.exists());
+
+        // 解析失败时指明格式
+        let json = dir.join(format!("vnc_des_config_{}.json", id));
+        std::fs::write(&json, "strict_mode = true").unwrap();
+        let err = VncDesConfig::from_file(&json).unwrap_err();
+        assert!(err.to_string().contains("无法解析JSON配置"));
+        assert_eq!(err.to_string().matches("JSON").count(), 1);
+
+        // 验证错误不被包装
+        let mut value = serde_json::to_value(VncDesConfig::default()).unwrap();
+        value["max_password_length"] = 0.into();
+        std::fs::write(&json, value.to_string()).unwrap();
+        assert_eq!(
+            VncDesConfig::from_file(&json).unwrap_err(),
+            VncDesConfig::from_json(&value.to_string()).unwrap_err()
+        );
+        std::fs::remove_file(&json).unwrap();
+
+        let toml = dir.join(format!("vnc_des_config_{}.toml", id));
+        std::fs::write(&toml, "{}").unwrap();
+        let err = VncDesConfig::from_file(&toml).unwrap_err();
+        if cfg!(feature = "toml") {
+            assert!(err.to_string().contains("无法解析TOML配置"));
+        } else {
+            assert!(matches!(err, VncDesError::Unsupported(_)));
+        }
+        assert!(err.to_string().contains("TOML"));
+        std::fs::remove_file(&toml).unwrap();
+
+        // 无扩展名按JSON处理
+        let plain = dir.join(format!("vnc_des_config_{}", id));
+        VncDesConfig::default().save_to_file(&plain).unwrap();
+        assert_eq!(
+            VncDesConfig::from_file(&plain).unwrap(),
+            VncDesConfig::default()
+        );
+        std::fs::remove_file(&plain).unwrap();
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_to_public_json() {
+        let config = VncDesConfig::new().with_key([0xAB, 0xCD, 0xEF, 0x01, 0x23, 0x45, 0x67, 0x89]);
+        let json = config.to_public_json().unwrap();
+
+        assert!(!json.contains(&config.key_as_hex()));
+        assert!(!json.contains("171"));
+        assert!(json.contains(&config.key_fingerprint()));
+
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["key_redacted"], true);
+        assert_eq!(value["encryption_key"], config.key_fingerprint());
+        assert_eq!(value["max_password_length"], 8);
+    }
+
+    #[test]
+    fn test_display_from_str_round_trip() {
+        let config = VncDesConfig::new()
+            .with_hex_key("0123456789abcdef")
+            .unwrap()
+            .with_strict_mode(true)
+            .with_auto_truncate(false)
+            .with_min_password_length(0)
+            .with_password_encoding(PasswordEncoding::Utf16Be)
+            .with_key_parity(ParityMode::SetOdd)
+            .with_reject_weak_keys(true)
+            .with_pad_byte(b' ');
+
+        let line = config.to_string();
+        assert!(line.ends_with(" pad=0x20"));
+        assert!(line.starts_with("key=0123456789abcdef strict=true truncate=false maxlen=8"));
+
+        let parsed: VncDesConfig = line.parse().unwrap();
+        assert_eq!(parsed, config);
+        assert_eq!(
+            VncDesConfig::default()
+                .to_string()
+                .parse::<VncDesConfig>()
+                .unwrap()
+                .to_string(),
+            VncDesConfig::default().to_string()
+        );
+
+        // 分号分隔、缺省字段
+        let parsed: VncDesConfig = "key=0123456789abcdef; strict=true".parse().unwrap();
+        assert!(parsed.strict_mode);
+        assert!(parsed.auto_truncate);
+        assert_eq!("pad=32".parse::<VncDesConfig>().unwrap().pad_byte, b' ');
+
+        for bad in [
+            "strict=yes",
+            "maxlen",
+            "color=red",
+            "key=0123",
+            "encoding=ebcdic",
+            "maxlen=0",
+            "pad=0x1ff",
+        ] {
+            assert!(bad.parse::<VncDesConfig>().is_err(), "{}", bad);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_hex_key_serialization() {
+        let json = VncDesConfig::default().to_json().unwrap();
+        assert!(json.contains(r#""encryption_key": "17526b06234e5807""#));
+
+        let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        value["encryption_key"] = serde_json::json!("0123456789ABCDEF");
+        let config = VncDesConfig::from_json(&value.to_string()).unwrap();
+        assert_eq!(
+            config.encryption_key,
+            [0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef]
+        );
+
+        // 长度错误或格式错误的密钥被拒绝
+        for bad in [
+            serde_json::json!("0123"),
+            serde_json::json!("zz23456789abcdef"),
+            serde_json::json!([1, 2, 3]),
+            serde_json::json!([1, 2, 3, 4, 5, 6, 7, 8, 9]),
+        ] {
+            value["encryption_key"] = bad;
+            assert!(VncDesConfig::from_json(&value.to_string()).is_err());
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_checksum() {
+        let config = VncDesConfig::default();
+        let checksum = config.checksum();
+        assert_eq!(checksum.len(), 64);
+        assert!(config.verify_checksum(&checksum));
+        assert!(config.verify_checksum(&checksum.to_uppercase()));
+
+        // 格式化方式不影响校验和
+        let reloaded = VncDesConfig::from_json(&config.to_json().unwrap()).unwrap();
+        assert_eq!(reloaded.checksum(), checksum);
+
+        // 任一字段变化都会改变校验和
+        let changed = config.clone().with_strict_mode(true);
+        assert_ne!(changed.checksum(), checksum);
+        assert!(!changed.verify_checksum(&checksum));
+
+        let changed = config.with_key([0u8; 8]);
+        assert_ne!(changed.checksum(), checksum);
     }
 }
@@ -0,0 +1,44 @@
+//! 8字节块的十六进制编码
+//!
+//! 不分配堆内存，可在 `no_std` 环境和常量求值中使用
+
+/// 小写十六进制字符表
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+/// 将8字节块编码为16个小写十六进制ASCII字节
+///
+/// 结果存储在栈上的数组中，需要 `&str` 时可用 `core::str::from_utf8` 转换（不会失败）。
+/// [`VncDesProcessor::to_hex_string`](crate::VncDesProcessor::to_hex_string) 是基于此函数的分配版本。
+///
+/// ```
+/// use vnc_des::to_hex_array;
+///
+/// const ENCODED: [u8; 16] = to_hex_array(&[0xdb, 0xd8, 0x3c, 0xfd, 0x72, 0x7a, 0x14, 0x58]);
+/// assert_eq!(&ENCODED, b"dbd83cfd727a1458");
+/// assert_eq!(core::str::from_utf8(&ENCODED).unwrap(), "dbd83cfd727a1458");
+/// ```
+pub const fn to_hex_array(block: &[u8; 8]) -> [u8; 16] {
+    let mut out = [0u8; 16];
+    let mut i = 0;
+    while i < 8 {
+        out[i * 2] = HEX_DIGITS[(block[i] >> 4) as usize];
+        out[i * 2 + 1] = HEX_DIGITS[(block[i] & 0x0f) as usize];
+        i += 1;
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_hex_array() {
+        assert_eq!(&to_hex_array(&[0u8; 8]), b"0000000000000000");
+        assert_eq!(&to_hex_array(&[0xff; 8]), b"ffffffffffffffff");
+        assert_eq!(
+            &to_hex_array(&[0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef]),
+            b"0123456789abcdef"
+        );
+    }
+}
@@ -0,0 +1,267 @@
+//! VNC认证挑战-响应
+//!
+//! RFC 6143 第7.2.2节：服务器发送16字节随机挑战，客户端以密码（截断或补0到8字节）
+//! 作为DES密钥，对挑战的两个8字节块分别加密后返回
+//!
+//! # 产品差异
+//!
+//! TightVNC、RealVNC、UltraVNC 在标准VNC认证（安全类型2）中构造DES密钥的方式相同：
+//! 密码字节截断/补0到8字节，每个字节反转位序后作为DES密钥。各产品的差异仅在于
+//! 本地保存密码时使用的固定密钥（见 [`VncProduct`](crate::VncProduct)）。
+//!
+//! UltraVNC 的 MS-Logon 使用独立的安全类型（MS-Logon II 基于Diffie-Hellman交换），
+//! 不属于DES挑战-响应，本模块不支持
+
+use crate::crypto::des::VncDesEngine;
+use core::fmt;
+
+/// VNC认证挑战长度（字节）
+pub const CHALLENGE_SIZE: usize = 16;
+
+/// 常量时间比较两个字节切片
+///
+/// 比较耗时只取决于长度，与首个不同字节的位置无关
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    diff_accumulator(a, b) == 0
+}
+
+/// 将两个等长切片逐字节异或后按位或到一个累加器中
+///
+/// 无论在哪个位置出现差异都会遍历全部字节，只在最后比较一次
+fn diff_accumulator(a: &[u8], b: &[u8]) -> u8 {
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y))
+}
+
+/// VNC认证（挑战-响应）
+pub struct VncAuth;
+
+impl VncAuth {
+    /// 将密码转换为8字节DES密钥（超出截断，不足补0）
+    pub fn password_key(password: &str) -> [u8; 8] {
+        let mut key = [0u8; 8];
+        let bytes = password.as_bytes();
+        let len = bytes.len().min(8);
+        key[..len].copy_from_slice(&bytes[..len]);
+        key
+    }
+
+    /// 使用密码计算挑战的响应
+    pub fn respond(password: &str, challenge: &[u8; CHALLENGE_SIZE]) -> [u8; CHALLENGE_SIZE] {
+        Self::respond_with_key(&Self::password_key(password), challenge)
+    }
+
+    /// 使用8字节密钥（已截断/补0的密码字节）计算挑战的响应
+    pub fn respond_with_key(
+        key: &[u8; 8],
+        challenge: &[u8; CHALLENGE_SIZE],
+    ) -> [u8; CHALLENGE_SIZE] {
+        VncDesEngine::new().encrypt_challenge(challenge, key)
+    }
+
+    /// 预先根据密码生成密钥，供多次认证（如断线重连）复用
+    pub fn prepare(password: &str) -> PreparedKey {
+        Self::prepare_with_key(&Self::password_key(password))
+    }
+
+    /// 预先根据8字节密钥生成密钥
+    pub fn prepare_with_key(key: &[u8; 8]) -> PreparedKey {
+        let mut engine = VncDesEngine::new();
+        engine.set_key(key, true);
+        PreparedKey { engine }
+    }
+}
+
+/// VNC认证（安全类型2）客户端/服务端处理器
+///
+/// 以密码作为DES密钥，计算或校验挑战的响应。与使用固定密钥保存密码的
+/// [`VncDesProcessor`](crate::VncDesProcessor) 不同，这里的密钥就是密码本身。
+///
+/// ```
+/// use vnc_des::VncChallengeProcessor;
+///
+/// let challenge = [0x42u8; 16];
+/// let processor = VncChallengeProcessor::new("password");
+/// let response = processor.respond(&challenge);
+/// assert!(VncChallengeProcessor::verify_response("password", &challenge, &response));
+/// ```
+#[derive(Debug)]
+pub struct VncChallengeProcessor {
+    key: PreparedKey,
+}
+
+impl VncChallengeProcessor {
+    /// 使用密码创建处理器（密码截断或补0到8字节）
+    pub fn new(password: &str) -> Self {
+        Self {
+            key: VncAuth::prepare(password),
+        }
+    }
+
+    /// 计算挑战的响应：分别加密挑战的两个8字节块
+    pub fn respond(&self, challenge: &[u8; CHALLENGE_SIZE]) -> [u8; CHALLENGE_SIZE] {
+        self.key.respond(challenge)
+    }
+
+    /// 校验客户端返回的响应（常量时间比较）
+    pub fn verify(
+        &self,
+        challenge: &[u8; CHALLENGE_SIZE],
+        response: &[u8; CHALLENGE_SIZE],
+    ) -> bool {
+        constant_time_eq(&self.respond(challenge), response)
+    }
+
+    /// 使用密码重新计算响应并与给定响应比较（常量时间比较）
+    pub fn verify_response(
+        password: &str,
+        challenge: &[u8; CHALLENGE_SIZE],
+        response: &[u8; CHALLENGE_SIZE],
+    ) -> bool {
+        Self::new(password).verify(challenge, response)
+    }
+}
+
+/// 预先生成的认证密钥
+///
+/// 持有已完成密钥编排的DES引擎，每次响应无需重新推导子密钥。
+/// 销毁时会清零子密钥。
+pub struct PreparedKey {
+    engine: VncDesEngine,
+}
+
+impl PreparedKey {
+    /// 计算挑战的响应
+    pub fn respond(&self, challenge: &[u8; CHALLENGE_SIZE]) -> [u8; CHALLENGE_SIZE] {
+        let mut response = [0u8; CHALLENGE_SIZE];
+
+        for (src, dst) in challenge.chunks_exact(8).zip(response.chunks_exact_mut(8)) {
+            let mut block = [0u8; 8];
+            block.copy_from_slice(src);
+            dst.copy_from_slice(&self.engine.process_block(&block));
+        }
+
+        response
+    }
+}
+
+impl fmt::Debug for PreparedKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PreparedKey").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq(b"abcdefgh", b"abcdefgh"));
+        assert!(!constant_time_eq(b"abcdefgh", b"xbcdefgh"));
+        assert!(!constant_time_eq(b"abcdefgh", b"abcdefgx"));
+        assert!(!constant_time_eq(b"abcdefgh", b"abc"));
+    }
+
+    #[test]
+    fn test_constant_time_eq_full_length() {
+        let expected = [0u8; 8];
+
+        // 首字节与末字节都不同：累加器同时包含两处差异，说明比较没有在首个差异处提前结束
+        let mut mismatch = expected;
+        mismatch[0] = 0x01;
+        mismatch[7] = 0x80;
+        assert_eq!(diff_accumulator(&expected, &mismatch), 0x81);
+        assert!(!constant_time_eq(&expected, &mismatch));
+
+        // 匹配时同样遍历全部字节，累加器为0
+        assert_eq!(diff_accumulator(&expected, &expected), 0);
+        assert!(constant_time_eq(&expected, &expected));
+
+        // 仅末字节不同也能检测到
+        let mut last = expected;
+        last[7] = 0x01;
+        assert!(!constant_time_eq(&expected, &last));
+    }
+
+    #[test]
+    fn test_respond_known_vector() {
+        let challenge: [u8; 16] = std::array::from_fn(|i| i as u8);
+        let response = VncAuth::respond("password", &challenge);
+
+        assert_eq!(hex::encode(response), "b866924125c8eebb9debc1db61c538e2");
+    }
+
+    #[test]
+    fn test_password_key() {
+        assert_eq!(VncAuth::password_key("pw"), [b'p', b'w', 0, 0, 0, 0, 0, 0]);
+        assert_eq!(VncAuth::password_key("password123"), *b"password");
+
+        // 超过8字节的部分不影响响应
+        let challenge = [0xA5u8; 16];
+        assert_eq!(
+            VncAuth::respond("password", &challenge),
+            VncAuth::respond("password123", &challenge)
+        );
+    }
+
+    #[test]
+    fn test_prepared_key() {
+        let prepared = VncAuth::prepare("password");
+
+        for seed in 0u8..4 {
+            let challenge: [u8; 16] = std::array::from_fn(|i| (i as u8).wrapping_mul(seed + 7));
+            assert_eq!(
+                prepared.respond(&challenge),
+                VncAuth::respond("password", &challenge)
+            );
+        }
+
+        assert_eq!(format!("{:?}", prepared), "PreparedKey { .. }");
+    }
+
+    #[test]
+    fn test_challenge_processor() {
+        let challenge: [u8; 16] = std::array::from_fn(|i| i as u8);
+        let processor = VncChallengeProcessor::new("password");
+        let response = processor.respond(&challenge);
+
+        assert_eq!(hex::encode(response), "b866924125c8eebb9debc1db61c538e2");
+        assert!(processor.verify(&challenge, &response));
+        assert!(VncChallengeProcessor::verify_response(
+            "password", &challenge, &response
+        ));
+        assert!(!VncChallengeProcessor::verify_response(
+            "passw0rd", &challenge, &response
+        ));
+
+        let mut tampered = response;
+        tampered[15] ^= 0x01;
+        assert!(!processor.verify(&challenge, &tampered));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_ultravnc_stored_password_uses_standard_key_construction() {
+        use crate::{VncDesConfig, VncDesProcessor, VncProduct};
+
+        // 合成向量：UltraVNC保存的 "password"（与TightVNC使用相同的固定密钥）
+        let processor = VncDesProcessor::new(VncDesConfig::for_product(VncProduct::UltraVnc));
+        let stored =
+            VncDesProcessor::to_hex_string(&processor.encrypt_password("password").unwrap());
+        assert_eq!(stored, "dbd83cfd727a1458");
+
+        let challenge: [u8; 16] = std::array::from_fn(|i| i as u8);
+        assert_eq!(
+            hex::encode(
+                processor
+                    .authenticate_from_stored(&stored, &challenge)
+                    .unwrap()
+            ),
+            "b866924125c8eebb9debc1db61c538e2"
+        );
+    }
+}
@@ -1,12 +1,22 @@
 //! VNC协议DES算法实现
 //!
 //! 这个模块实现了符合VNC协议标准（RFC 6143）的DES认证算法
-//! 注意：这是VNC协议特化的DES算法，与标准DES有所不同
+//! 注意：这是VNC协议特化的DES算法，与标准DES有所不同：
+//! VNC在使用密钥前会反转每个密钥字节的位序。
+//! 可通过 [`VncDesEngine::standard_des_block`] 与标准DES-ECB的输出进行对比
+//!
+//! 本模块不依赖标准库与堆分配，关闭 `std` 特性后仍可使用
+//! （[`encrypt_block`](VncDesEngine::encrypt_block)、[`decrypt_block`](VncDesEngine::decrypt_block)）
 
-use crate::error::Result;
+use crate::crypto::scrub::scrub;
+#[cfg(feature = "std")]
+use crate::error::{Result, VncDesError};
+#[cfg(feature = "std")]
+use sha2::{Digest, Sha256};
 
 /// VNC协议特化的DES实现常量和表
 /// 字节位数组 - 已反转用于VNC兼容性
+#[cfg(any(test, not(feature = "fast-keyschedule")))]
 const BYTEBIT: [u16; 8] = [0o01, 0o02, 0o04, 0o010, 0o020, 0o040, 0o0100, 0o0200];
 
 const BIGBYTE: [u32; 24] = [
@@ -116,11 +126,39 @@ const SP8: [u32; 64] = [
     0x10041040, 0x00041000, 0x00041000, 0x00001040, 0x00001040, 0x00040040, 0x10000000, 0x10041000,
 ];
 
+/// 标准DES的4个弱密钥（标准位序，奇校验）
+const DES_WEAK_KEYS: [[u8; 8]; 4] = [
+    [0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01],
+    [0xFE, 0xFE, 0xFE, 0xFE, 0xFE, 0xFE, 0xFE, 0xFE],
+    [0xE0, 0xE0, 0xE0, 0xE0, 0xF1, 0xF1, 0xF1, 0xF1],
+    [0x1F, 0x1F, 0x1F, 0x1F, 0x0E, 0x0E, 0x0E, 0x0E],
+];
+
+/// 标准DES的12个半弱密钥（6对，标准位序，奇校验）
+///
+/// 同一对中的两个密钥互为逆运算：用其中一个加密的结果可以用另一个再次"加密"还原
+const DES_SEMIWEAK_KEYS: [[u8; 8]; 12] = [
+    [0x01, 0xFE, 0x01, 0xFE, 0x01, 0xFE, 0x01, 0xFE],
+    [0xFE, 0x01, 0xFE, 0x01, 0xFE, 0x01, 0xFE, 0x01],
+    [0x1F, 0xE0, 0x1F, 0xE0, 0x0E, 0xF1, 0x0E, 0xF1],
+    [0xE0, 0x1F, 0xE0, 0x1F, 0xF1, 0x0E, 0xF1, 0x0E],
+    [0x01, 0xE0, 0x01, 0xE0, 0x01, 0xF1, 0x01, 0xF1],
+    [0xE0, 0x01, 0xE0, 0x01, 0xF1, 0x01, 0xF1, 0x01],
+    [0x1F, 0xFE, 0x1F, 0xFE, 0x0E, 0xFE, 0x0E, 0xFE],
+    [0xFE, 0x1F, 0xFE, 0x1F, 0xFE, 0x0E, 0xFE, 0x0E],
+    [0x01, 0x1F, 0x01, 0x1F, 0x01, 0x0E, 0x01, 0x0E],
+    [0x1F, 0x01, 0x1F, 0x01, 0x0E, 0x01, 0x0E, 0x01],
+    [0xE0, 0xFE, 0xE0, 0xFE, 0xF1, 0xFE, 0xF1, 0xFE],
+    [0xFE, 0xE0, 0xFE, 0xE0, 0xFE, 0xF1, 0xFE, 0xF1],
+];
+
 /// VNC DES引擎 - 完全基于TightVNC参考实现
 #[derive(Debug, Clone)]
 pub struct VncDesEngine {
     /// 子密钥数组
     kn_l: [u32; 32],
+    /// 是否拒绝弱密钥和半弱密钥（见 [`try_deskey`](Self::try_deskey)）
+    reject_weak_keys: bool,
 }
 
 impl Default for VncDesEngine {
@@ -129,19 +167,163 @@ impl Default for VncDesEngine {
     }
 }
 
+impl Drop for VncDesEngine {
+    fn drop(&mut self) {
+        self.clear_key();
+    }
+}
+
 impl VncDesEngine {
     /// 创建新的DES引擎实例
     pub fn new() -> Self {
-        Self { kn_l: [0; 32] }
+        Self {
+            kn_l: [0; 32],
+            reject_weak_keys: false,
+        }
+    }
+
+    /// 设置是否拒绝弱密钥和半弱密钥
+    ///
+    /// 启用后 [`try_deskey`](Self::try_deskey)、[`encrypt`](Self::encrypt)、
+    /// [`decrypt`](Self::decrypt) 遇到这类密钥时返回错误
+    pub fn set_reject_weak_keys(&mut self, reject: bool) {
+        self.reject_weak_keys = reject;
+    }
+
+    /// 检查密钥是否为DES弱密钥
+    ///
+    /// VNC会反转每个密钥字节的位序后再交给DES，因此先反转位序，
+    /// 再忽略校验位与标准弱密钥表比较
+    pub fn is_weak_key(key: &[u8; 8]) -> bool {
+        Self::matches_any(key, &DES_WEAK_KEYS)
+    }
+
+    /// 检查密钥是否为DES半弱密钥（比较方式同 [`is_weak_key`](Self::is_weak_key)）
+    pub fn is_semiweak_key(key: &[u8; 8]) -> bool {
+        Self::matches_any(key, &DES_SEMIWEAK_KEYS)
+    }
+
+    /// 按VNC位序、忽略校验位，判断密钥是否在给定的标准位序密钥表中
+    fn matches_any(key: &[u8; 8], list: &[[u8; 8]]) -> bool {
+        list.iter().any(|listed| {
+            key.iter()
+                .zip(listed)
+                .all(|(k, l)| k.reverse_bits() & 0xFE == l & 0xFE)
+        })
+    }
+
+    /// 计算指定密钥的加密子密钥表指纹（SHA-256前16个十六进制字符）
+    ///
+    /// 只有子密钥表相同（即仅校验位不同）的密钥才有相同指纹，便于在问题报告中
+    /// 对比两端实际使用的密钥，而不暴露密钥本身
+    #[cfg(feature = "std")]
+    pub fn schedule_fingerprint(key: &[u8; 8]) -> String {
+        let mut engine = Self::new();
+        engine.deskey(key, true);
+
+        let mut hasher = Sha256::new();
+        for word in &engine.kn_l {
+            hasher.update(word.to_be_bytes());
+        }
+        engine.clear_key();
+
+        hex::encode(&hasher.finalize()[..8])
+    }
+
+    /// 导出指定密钥的加密子密钥表（32个子密钥字，仅用于调试）
+    #[cfg(feature = "debug-internals")]
+    pub fn dump_schedule(key: &[u8; 8]) -> [u32; 32] {
+        let mut engine = Self::new();
+        engine.deskey(key, true);
+        engine.kn_l
+    }
+
+    /// 比较两个密钥的加密子密钥表，返回不同子密钥字的下标（仅用于调试）
+    #[cfg(feature = "debug-internals")]
+    pub fn compare_schedules(key_a: &[u8; 8], key_b: &[u8; 8]) -> Vec<usize> {
+        let schedule_a = Self::dump_schedule(key_a);
+        let schedule_b = Self::dump_schedule(key_b);
+        (0..schedule_a.len())
+            .filter(|&i| schedule_a[i] != schedule_b[i])
+            .collect()
+    }
+
+    /// 只读访问当前的子密钥表（仅用于调试和与其他DES实现交叉核对）
+    ///
+    /// 表的格式与TightVNC参考实现的 `KnL` 一致：每轮两个字，共16轮；
+    /// 解密密钥的轮次顺序与加密相反。未设置密钥时为全零
+    ///
+    /// ```
+    /// use vnc_des::VncDesEngine;
+    ///
+    /// let key = [0x17, 0x52, 0x6b, 0x06, 0x23, 0x4e, 0x58, 0x07];
+    /// let mut engine = VncDesEngine::new();
+    /// engine.set_key(&key, true);
+    /// assert_eq!(engine.subkeys(), &VncDesEngine::dump_schedule(&key));
+    /// ```
+    #[cfg(feature = "debug-internals")]
+    pub fn subkeys(&self) -> &[u32; 32] {
+        &self.kn_l
+    }
+
+    /// 使用预先计算的子密钥表创建引擎（仅用于调试）
+    ///
+    /// 不做任何校验，子密钥表格式见 [`subkeys`](Self::subkeys)。创建后可直接调用
+    /// [`process_block`](Self::process_block)；加密还是解密取决于子密钥表本身
+    #[cfg(feature = "debug-internals")]
+    pub fn from_subkeys(subkeys: [u32; 32]) -> Self {
+        Self {
+            kn_l: subkeys,
+            reject_weak_keys: false,
+        }
+    }
+
+    /// 是否仍持有非零的子密钥表
+    #[cfg(test)]
+    pub(crate) fn has_key_schedule(&self) -> bool {
+        self.kn_l.iter().any(|&word| word != 0)
     }
 
     /// 清空密钥
+    ///
+    /// 清零操作不会被编译器作为无用写入优化掉；引擎销毁时也会自动调用
     pub fn clear_key(&mut self) {
-        self.kn_l.fill(0);
+        scrub(&mut self.kn_l);
     }
 
     /// 设置DES密钥（参考实现兼容）
+    ///
+    /// 启用 `fast-keyschedule` 特性时使用预计算查找表生成子密钥，结果与参考实现逐位一致
     pub fn deskey(&mut self, hex_key: &[u8; 8], encrypt: bool) {
+        #[cfg(feature = "fast-keyschedule")]
+        let kn = fast_keyschedule::raw_schedule(hex_key, encrypt);
+        #[cfg(not(feature = "fast-keyschedule"))]
+        let kn = Self::raw_schedule_reference(hex_key, encrypt);
+
+        self.cookey(&kn);
+        log_trace!(
+            "DES密钥编排完成（{}）",
+            if encrypt { "加密" } else { "解密" }
+        );
+    }
+
+    /// 设置DES密钥，启用弱密钥拒绝时对弱密钥和半弱密钥返回错误
+    #[cfg(feature = "std")]
+    pub fn try_deskey(&mut self, hex_key: &[u8; 8], encrypt: bool) -> Result<()> {
+        if self.reject_weak_keys && (Self::is_weak_key(hex_key) || Self::is_semiweak_key(hex_key)) {
+            log_debug!("拒绝DES弱密钥或半弱密钥");
+            return Err(VncDesError::invalid_key_format("密钥为DES弱密钥或半弱密钥"));
+        }
+
+        self.deskey(hex_key, encrypt);
+        Ok(())
+    }
+
+    /// 逐位计算PC1/PC2置换后的原始子密钥（参考实现）
+    ///
+    /// 启用 `fast-keyschedule` 特性时仅在测试中作为对照
+    #[cfg(any(test, not(feature = "fast-keyschedule")))]
+    fn raw_schedule_reference(hex_key: &[u8; 8], encrypt: bool) -> [u32; 32] {
         let mut pc1m = [0u8; 56];
         let mut pcr = [0u8; 56];
         let mut kn = [0u32; 32];
@@ -157,20 +339,17 @@ impl VncDesEngine {
             };
         }
 
-        for i in 0..16 {
+        for (i, &rot) in TOTROT.iter().enumerate() {
+            let rot = rot as usize;
             let m = if encrypt { i << 1 } else { (15 - i) << 1 };
             let n = m + 1;
             kn[m] = 0;
             kn[n] = 0;
 
-            for j in 0..28 {
-                let l = j + TOTROT[i] as usize;
-                pcr[j] = if l < 28 { pc1m[l] } else { pc1m[l - 28] };
-            }
-
-            for j in 28..56 {
-                let l = j + TOTROT[i] as usize;
-                pcr[j] = if l < 56 { pc1m[l] } else { pc1m[l - 28] };
+            for (j, bit) in pcr.iter_mut().enumerate() {
+                let l = j + rot;
+                let limit = if j < 28 { 28 } else { 56 };
+                *bit = if l < limit { pc1m[l] } else { pc1m[l - 28] };
             }
 
             for j in 0..24 {
@@ -183,7 +362,7 @@ impl VncDesEngine {
             }
         }
 
-        self.cookey(&kn);
+        kn
     }
 
     /// 处理密钥（参考实现）
@@ -256,17 +435,17 @@ impl VncDesEngine {
         work = ((right >> 8) ^ leftt) & 0x00ff00ff;
         leftt ^= work;
         right ^= work << 8;
-        right = ((right << 1) | ((right >> 31) & 1)) & 0xffffffff;
+        right = right.rotate_left(1);
         work = (leftt ^ right) & 0xaaaaaaaa;
         leftt ^= work;
         right ^= work;
-        leftt = ((leftt << 1) | ((leftt >> 31) & 1)) & 0xffffffff;
+        leftt = leftt.rotate_left(1);
 
         // 16 rounds
         for round in 0..8 {
             let key_idx = round * 4;
 
-            work = ((right << 28) | (right >> 4)) ^ self.kn_l[key_idx];
+            work = right.rotate_right(4) ^ self.kn_l[key_idx];
             let mut fval = SP7[(work & 0x3f) as usize]
                 | SP5[((work >> 8) & 0x3f) as usize]
                 | SP3[((work >> 16) & 0x3f) as usize]
@@ -280,7 +459,7 @@ impl VncDesEngine {
 
             leftt ^= fval;
 
-            work = ((leftt << 28) | (leftt >> 4)) ^ self.kn_l[key_idx + 2];
+            work = leftt.rotate_right(4) ^ self.kn_l[key_idx + 2];
             fval = SP7[(work & 0x3f) as usize]
                 | SP5[((work >> 8) & 0x3f) as usize]
                 | SP3[((work >> 16) & 0x3f) as usize]
@@ -296,11 +475,11 @@ impl VncDesEngine {
         }
 
         // Final permutation
-        right = (right << 31) | (right >> 1);
+        right = right.rotate_right(1);
         work = (leftt ^ right) & 0xaaaaaaaa;
         leftt ^= work;
         right ^= work;
-        leftt = (leftt << 31) | (leftt >> 1);
+        leftt = leftt.rotate_right(1);
         work = ((leftt >> 8) ^ right) & 0x00ff00ff;
         right ^= work;
         leftt ^= work << 8;
@@ -319,29 +498,232 @@ impl VncDesEngine {
     }
 
     /// 执行DES加密/解密（参考实现）
-    pub fn des(&mut self, from: &[u8; 8], to: &mut [u8; 8]) {
+    pub fn des(&self, from: &[u8; 8], to: &mut [u8; 8]) {
         let mut work = Self::scrunch(from);
         self.desfunc(&mut work);
         *to = Self::unscrun(&work);
     }
 
+    /// 反转密钥每个字节的位序（VNC与标准DES之间的密钥转换）
+    ///
+    /// VNC把密码字节的最低位当作DES密钥字节的最高位使用。本引擎的 `BYTEBIT` 表已内置
+    /// 这一反转，因此 `engine.encrypt_block(P, K)` 与标准DES库的
+    /// `DES_encrypt(P, reverse_key_bits(K))` 结果相同。该变换是自逆的：调用两次得到原密钥
+    ///
+    /// ```
+    /// use vnc_des::VncDesEngine;
+    ///
+    /// // VNC密码 "password" 对应的标准DES密钥
+    /// let des_key = VncDesEngine::reverse_key_bits(b"password");
+    /// assert_eq!(des_key, [0x0e, 0x86, 0xce, 0xce, 0xee, 0xf6, 0x4e, 0x26]);
+    /// assert_eq!(VncDesEngine::reverse_key_bits(&des_key), *b"password");
+    /// ```
+    pub fn reverse_key_bits(key: &[u8; 8]) -> [u8; 8] {
+        key.map(u8::reverse_bits)
+    }
+
+    /// 使用标准DES（不做VNC位序反转）加密单个8字节块
+    ///
+    /// VNC会先反转每个密钥字节的位序再交给DES，本引擎的 `BYTEBIT` 表已内置这一反转。
+    /// 此函数在调用引擎前预先反转密钥位序以抵消内置反转，因此结果与标准DES-ECB一致，
+    /// 可与 `openssl enc -des-ecb -nopad -K <KEY>` 的输出直接比较：
+    ///
+    /// ```text
+    /// standard_des_block(K, P) == VNC_DES(reverse_key_bits(K), P)
+    /// ```
+    pub fn standard_des_block(key: &[u8; 8], block: &[u8; 8]) -> [u8; 8] {
+        let reversed_key = Self::reverse_key_bits(key);
+
+        let mut engine = Self::new();
+        let mut output = [0u8; 8];
+        engine.deskey(&reversed_key, true);
+        engine.des(block, &mut output);
+        engine.clear_key();
+        output
+    }
+
+    /// 使用指定密钥加密单个8字节块，不分配内存，子密钥用后即清零
+    ///
+    /// 不检查弱密钥（见 [`set_reject_weak_keys`](Self::set_reject_weak_keys)），可在 `no_std` 环境使用
+    pub fn encrypt_block(&mut self, block: &[u8; 8], key: &[u8; 8]) -> [u8; 8] {
+        self.crypt_block(block, key, true)
+    }
+
+    /// 使用指定密钥解密单个8字节块（见 [`encrypt_block`](Self::encrypt_block)）
+    pub fn decrypt_block(&mut self, block: &[u8; 8], key: &[u8; 8]) -> [u8; 8] {
+        self.crypt_block(block, key, false)
+    }
+
+    /// 计算VNC认证（RFC 6143 第7.2.2节）16字节挑战的响应
+    ///
+    /// 只编排一次密钥，用同一组子密钥分别加密挑战的两个8字节块，子密钥用后即清零。
+    /// `key` 直接传入截断或补0到8字节的密码字节，VNC要求的密钥字节位序反转由引擎
+    /// 内部完成，调用方不应再自行反转。需要多次响应同一密码时见
+    /// [`VncAuth::prepare`](crate::VncAuth::prepare)
+    ///
+    /// ```
+    /// use vnc_des::{VncAuth, VncDesEngine};
+    ///
+    /// let challenge = [0x42u8; 16];
+    /// let key = VncAuth::password_key("password");
+    /// let response = VncDesEngine::new().encrypt_challenge(&challenge, &key);
+    /// assert_eq!(response, VncAuth::respond("password", &challenge));
+    /// ```
+    pub fn encrypt_challenge(&mut self, challenge: &[u8; 16], key: &[u8; 8]) -> [u8; 16] {
+        self.set_key(key, true);
+        let mut response = [0u8; 16];
+        for (src, dst) in challenge.chunks_exact(8).zip(response.chunks_exact_mut(8)) {
+            let mut block = [0u8; 8];
+            block.copy_from_slice(src);
+            dst.copy_from_slice(&self.process_block(&block));
+        }
+        self.clear_key();
+        response
+    }
+
+    fn crypt_block(&mut self, block: &[u8; 8], key: &[u8; 8], encrypt: bool) -> [u8; 8] {
+        self.set_key(key, encrypt);
+        let output = self.process_block(block);
+        self.clear_key();
+        output
+    }
+
+    /// 编排密钥，供随后多次调用 [`process_block`](Self::process_block)
+    ///
+    /// 与一次性的 [`encrypt_block`](Self::encrypt_block) 不同，子密钥会一直保留到
+    /// 调用 [`clear_key`](Self::clear_key)、再次设置密钥或引擎被销毁为止。
+    /// 不检查弱密钥，可在 `no_std` 环境使用
+    ///
+    /// ```
+    /// use vnc_des::VncDesEngine;
+    ///
+    /// let key = [0x17, 0x52, 0x6b, 0x06, 0x23, 0x4e, 0x58, 0x07];
+    /// let mut engine = VncDesEngine::new();
+    /// engine.set_key(&key, true);
+    /// let first = engine.process_block(b"password");
+    /// let second = engine.process_block(b"12345678");
+    /// engine.clear_key();
+    ///
+    /// assert_eq!(first, VncDesEngine::new().encrypt_block(b"password", &key));
+    /// # let _ = second;
+    /// ```
+    pub fn set_key(&mut self, key: &[u8; 8], encrypt: bool) {
+        self.deskey(key, encrypt);
+    }
+
+    /// 使用已编排的子密钥处理单个8字节块（加密或解密取决于 [`set_key`](Self::set_key) 的参数）
+    pub fn process_block(&self, block: &[u8; 8]) -> [u8; 8] {
+        let mut output = [0u8; 8];
+        self.des(block, &mut output);
+        output
+    }
+
     /// 加密8字节块（参考实现兼容）
+    #[cfg(feature = "std")]
     pub fn encrypt(&mut self, dst: &mut [u8; 8], src: &[u8; 8], key: &[u8; 8]) -> Result<()> {
-        self.deskey(key, true);
+        self.try_deskey(key, true)?;
         self.des(src, dst);
         self.clear_key();
         Ok(())
     }
 
     /// 解密8字节块（参考实现兼容）
+    #[cfg(feature = "std")]
     pub fn decrypt(&mut self, dst: &mut [u8; 8], src: &[u8; 8], key: &[u8; 8]) -> Result<()> {
-        self.deskey(key, false);
+        self.try_deskey(key, false)?;
         self.des(src, dst);
         self.clear_key();
         Ok(())
     }
 }
 
+/// 基于预计算查找表的子密钥生成（`fast-keyschedule` 特性）
+///
+/// PC1/PC2置换与循环移位只是重排密钥位，因此原始子密钥的每一位都恰好来自一个密钥位。
+/// 编译期为每个半字节位置（16个）的每种取值（16种）预先计算其贡献的子密钥位，
+/// 运行时只需16次查表并按位或，替代参考实现中逐位的嵌套循环。查找表约32KB。
+#[cfg(feature = "fast-keyschedule")]
+mod fast_keyschedule {
+    use super::{BIGBYTE, PC1, PC2, TOTROT};
+
+    /// 每个密钥位（字节序号 * 8 + 位序号）对加密顺序原始子密钥的贡献
+    const fn key_bit_table() -> [[u32; 32]; 64] {
+        let mut table = [[0u32; 32]; 64];
+        let mut i = 0;
+        while i < 16 {
+            let rot = TOTROT[i] as usize;
+            let mut j = 0;
+            while j < 48 {
+                // 与参考实现相同：pcr[k] = pc1m[移位后的位置]
+                let k = PC2[j] as usize;
+                let l = k + rot;
+                let limit = if k < 28 { 28 } else { 56 };
+                let source = if l < limit { l } else { l - 28 };
+                let key_bit = PC1[source] as usize;
+
+                let word = (i << 1) + j / 24;
+                table[key_bit][word] |= BIGBYTE[j % 24];
+                j += 1;
+            }
+            i += 1;
+        }
+        table
+    }
+
+    /// 每个半字节位置、每种取值对应的原始子密钥贡献
+    const fn nibble_table() -> [[[u32; 32]; 16]; 16] {
+        let bits = key_bit_table();
+        let mut table = [[[0u32; 32]; 16]; 16];
+        let mut position = 0;
+        while position < 16 {
+            let mut value = 0;
+            while value < 16 {
+                let mut bit = 0;
+                while bit < 4 {
+                    if value & (1 << bit) != 0 {
+                        let key_bit = position * 4 + bit;
+                        let mut word = 0;
+                        while word < 32 {
+                            table[position][value][word] |= bits[key_bit][word];
+                            word += 1;
+                        }
+                    }
+                    bit += 1;
+                }
+                value += 1;
+            }
+            position += 1;
+        }
+        table
+    }
+
+    static NIBBLE_TABLE: [[[u32; 32]; 16]; 16] = nibble_table();
+
+    /// 查表计算原始子密钥，结果与参考实现逐位一致
+    pub(super) fn raw_schedule(hex_key: &[u8; 8], encrypt: bool) -> [u32; 32] {
+        let mut kn = [0u32; 32];
+        for (i, &byte) in hex_key.iter().enumerate() {
+            let low = &NIBBLE_TABLE[i * 2][(byte & 0x0F) as usize];
+            let high = &NIBBLE_TABLE[i * 2 + 1][(byte >> 4) as usize];
+            for (word, (l, h)) in kn.iter_mut().zip(low.iter().zip(high)) {
+                *word |= l | h;
+            }
+        }
+
+        if !encrypt {
+            // 解密时子密钥按轮次逆序排列
+            let mut reversed = [0u32; 32];
+            for round in 0..16 {
+                reversed[(15 - round) << 1] = kn[round << 1];
+                reversed[((15 - round) << 1) + 1] = kn[(round << 1) + 1];
+            }
+            kn = reversed;
+        }
+
+        kn
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -352,6 +734,180 @@ mod tests {
         assert_eq!(engine.kn_l, [0; 32]);
     }
 
+    #[test]
+    fn test_drop_clears_key_schedule() {
+        let mut engine = core::mem::ManuallyDrop::new(VncDesEngine::new());
+        engine.deskey(&[0x17, 0x52, 0x6b, 0x06, 0x23, 0x4e, 0x58, 0x07], true);
+        assert!(engine.has_key_schedule());
+
+        // SAFETY: 只运行Drop，不释放内存；之后仅读取 Copy 字段检查清零结果
+        unsafe { core::mem::ManuallyDrop::drop(&mut engine) };
+        assert!(!engine.has_key_schedule());
+    }
+
+    #[test]
+    fn test_split_key_schedule_matches_one_shot() {
+        let key = [0x17, 0x52, 0x6b, 0x06, 0x23, 0x4e, 0x58, 0x07];
+        let blocks: [[u8; 8]; 4] = [*b"password", *b"12345678", [0u8; 8], [0xffu8; 8]];
+        let mut one_shot = VncDesEngine::new();
+
+        let mut engine = VncDesEngine::new();
+        engine.set_key(&key, true);
+        let encrypted = blocks.map(|block| engine.process_block(&block));
+        for (block, output) in blocks.iter().zip(&encrypted) {
+            assert_eq!(*output, one_shot.encrypt_block(block, &key));
+        }
+
+        engine.set_key(&key, false);
+        for (block, output) in blocks.iter().zip(&encrypted) {
+            assert_eq!(engine.process_block(output), *block);
+            assert_eq!(one_shot.decrypt_block(output, &key), *block);
+        }
+
+        engine.clear_key();
+        assert!(!engine.has_key_schedule());
+    }
+
+    #[test]
+    fn test_encrypt_block() {
+        let mut engine = VncDesEngine::new();
+        let key = [23, 82, 107, 6, 35, 78, 88, 7];
+        let block = *b"test\0\0\0\0";
+        let expected = [0x2f, 0x98, 0x1d, 0xc5, 0x48, 0xe0, 0x9e, 0xc2];
+
+        assert_eq!(engine.encrypt_block(&block, &key), expected);
+        assert_eq!(engine.decrypt_block(&expected, &key), block);
+        assert_eq!(engine.kn_l, [0; 32]);
+    }
+
+    #[test]
+    fn test_encrypt_challenge() {
+        let mut engine = VncDesEngine::new();
+        // 密码 "password" 的原始字节，位序反转由引擎完成
+        let key = *b"password";
+        let challenge: [u8; 16] = core::array::from_fn(|i| i as u8);
+        let expected = [
+            0xb8, 0x66, 0x92, 0x41, 0x25, 0xc8, 0xee, 0xbb, 0x9d, 0xeb, 0xc1, 0xdb, 0x61, 0xc5,
+            0x38, 0xe2,
+        ];
+
+        assert_eq!(engine.encrypt_challenge(&challenge, &key), expected);
+        assert_eq!(engine.kn_l, [0; 32]);
+
+        // 两个块使用同一组子密钥，与逐块加密一致
+        let mut first = [0u8; 8];
+        first.copy_from_slice(&challenge[..8]);
+        assert_eq!(engine.encrypt_block(&first, &key), expected[..8]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_is_weak_key() {
+        // VNC位序下的全0/全1密钥
+        assert!(VncDesEngine::is_weak_key(&[0x00; 8]));
+        assert!(VncDesEngine::is_weak_key(&[0xFF; 8]));
+        // 标准弱密钥 0101010101010101 在VNC位序下为 8080808080808080
+        assert!(VncDesEngine::is_weak_key(&[0x80; 8]));
+        assert!(!VncDesEngine::is_weak_key(&[23, 82, 107, 6, 35, 78, 88, 7]));
+
+        // 弱密钥下加密两次等于原文
+        let mut engine = VncDesEngine::new();
+        let plain = *b"weakkey!";
+        let (mut once, mut twice) = ([0u8; 8], [0u8; 8]);
+        // 标准弱密钥 E0E0E0E0F1F1F1F1 的VNC位序形式
+        let key = [0x07, 0x07, 0x07, 0x07, 0x8F, 0x8F, 0x8F, 0x8F];
+        assert!(VncDesEngine::is_weak_key(&key));
+        engine.encrypt(&mut once, &plain, &key).unwrap();
+        engine.encrypt(&mut twice, &once, &key).unwrap();
+        assert_eq!(twice, plain);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_documented_weak_and_semiweak_keys() {
+        for weak in &DES_WEAK_KEYS {
+            let vnc_key = weak.map(u8::reverse_bits);
+            assert!(VncDesEngine::is_weak_key(&vnc_key), "{:02x?}", weak);
+            assert!(!VncDesEngine::is_semiweak_key(&vnc_key));
+        }
+        for semiweak in &DES_SEMIWEAK_KEYS {
+            let vnc_key = semiweak.map(u8::reverse_bits);
+            assert!(VncDesEngine::is_semiweak_key(&vnc_key), "{:02x?}", semiweak);
+            assert!(!VncDesEngine::is_weak_key(&vnc_key));
+        }
+
+        let tightvnc = [0x17, 0x52, 0x6b, 0x06, 0x23, 0x4e, 0x58, 0x07];
+        assert!(!VncDesEngine::is_weak_key(&tightvnc));
+        assert!(!VncDesEngine::is_semiweak_key(&tightvnc));
+
+        // 半弱密钥对互为逆运算
+        let mut engine = VncDesEngine::new();
+        let plain = *b"semiweak";
+        let (mut once, mut twice) = ([0u8; 8], [0u8; 8]);
+        let key_a = DES_SEMIWEAK_KEYS[0].map(u8::reverse_bits);
+        let key_b = DES_SEMIWEAK_KEYS[1].map(u8::reverse_bits);
+        engine.encrypt(&mut once, &plain, &key_a).unwrap();
+        engine.encrypt(&mut twice, &once, &key_b).unwrap();
+        assert_eq!(twice, plain);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_reject_weak_keys() {
+        let mut engine = VncDesEngine::new();
+        let mut out = [0u8; 8];
+        assert!(engine.encrypt(&mut out, b"password", &[0x00; 8]).is_ok());
+
+        engine.set_reject_weak_keys(true);
+        assert!(engine.try_deskey(&[0x00; 8], true).is_err());
+        assert!(engine
+            .encrypt(
+                &mut out,
+                b"password",
+                &DES_SEMIWEAK_KEYS[4].map(u8::reverse_bits)
+            )
+            .is_err());
+        assert!(engine
+            .decrypt(
+                &mut out,
+                b"password",
+                &[0x17, 0x52, 0x6b, 0x06, 0x23, 0x4e, 0x58, 0x07]
+            )
+            .is_ok());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_standard_des_block() {
+        // 经典DES测试向量，可用以下命令复现:
+        // echo -n 0123456789ABCDEF | xxd -r -p | openssl enc -des-ecb -nopad -K 133457799BBCDFF1
+        let key = [0x13, 0x34, 0x57, 0x79, 0x9B, 0xBC, 0xDF, 0xF1];
+        let plain = [0x01, 0x23, 0x45, 0x67, 0x89, 0xAB, 0xCD, 0xEF];
+        let expected = [0x85, 0xE8, 0x13, 0x54, 0x0F, 0x0A, 0xB4, 0x05];
+        assert_eq!(VncDesEngine::standard_des_block(&key, &plain), expected);
+
+        // 同一密钥直接用于VNC DES（位序反转）得到不同结果
+        let mut engine = VncDesEngine::new();
+        let mut vnc_output = [0u8; 8];
+        engine.encrypt(&mut vnc_output, &plain, &key).unwrap();
+        assert_ne!(vnc_output, expected);
+
+        // 标准DES使用 K 等价于本引擎使用 reverse_key_bits(K)
+        let reversed_key = VncDesEngine::reverse_key_bits(&key);
+        assert_eq!(
+            reversed_key,
+            [0xC8, 0x2C, 0xEA, 0x9E, 0xD9, 0x3D, 0xFB, 0x8F]
+        );
+        engine
+            .encrypt(&mut vnc_output, &plain, &reversed_key)
+            .unwrap();
+        assert_eq!(vnc_output, expected);
+        assert_eq!(engine.encrypt_block(&plain, &reversed_key), expected);
+
+        // 自逆
+        assert_eq!(VncDesEngine::reverse_key_bits(&reversed_key), key);
+    }
+
     #[test]
     fn test_scrunch_unscrun() {
         let data = [0x01, 0x23, 0x45, 0x67, 0x89, 0xAB, 0xCD, 0xEF];
@@ -360,6 +916,7 @@ mod tests {
         assert_eq!(data, unpacked);
     }
 
+    #[cfg(feature = "std")]
     #[test]
     fn test_encryption_compatibility() {
         let mut engine = VncDesEngine::new();
@@ -381,4 +938,79 @@ mod tests {
         let expected = [0x2f, 0x98, 0x1d, 0xc5, 0x48, 0xe0, 0x9e, 0xc2];
         assert_eq!(encrypted, expected);
     }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_schedule_fingerprint() {
+        let key = [0x17, 0x52, 0x6b, 0x06, 0x23, 0x4e, 0x58, 0x07];
+        let fingerprint = VncDesEngine::schedule_fingerprint(&key);
+        assert_eq!(fingerprint.len(), 16);
+
+        // 仅校验位（VNC位序下的最高位）不同的密钥产生相同的子密钥表
+        let mut parity = key;
+        parity[3] ^= 0x80;
+        assert_eq!(VncDesEngine::schedule_fingerprint(&parity), fingerprint);
+
+        let mut other = key;
+        other[3] ^= 0x01;
+        assert_ne!(VncDesEngine::schedule_fingerprint(&other), fingerprint);
+    }
+
+    #[cfg(feature = "debug-internals")]
+    #[test]
+    fn test_compare_schedules() {
+        let key = [0x17, 0x52, 0x6b, 0x06, 0x23, 0x4e, 0x58, 0x07];
+        assert!(VncDesEngine::compare_schedules(&key, &key).is_empty());
+        assert_ne!(VncDesEngine::dump_schedule(&key), [0; 32]);
+
+        let mut other = key;
+        other[0] ^= 0x01;
+        assert!(!VncDesEngine::compare_schedules(&key, &other).is_empty());
+
+        // VNC会反转位序，因此每个字节的最高位才是DES校验位，不参与子密钥生成
+        let mut parity = key;
+        parity[0] ^= 0x80;
+        assert!(VncDesEngine::compare_schedules(&key, &parity).is_empty());
+    }
+
+    #[cfg(feature = "debug-internals")]
+    #[test]
+    fn test_from_subkeys() {
+        let key = [0x17, 0x52, 0x6b, 0x06, 0x23, 0x4e, 0x58, 0x07];
+        let mut reference = VncDesEngine::new();
+        let encrypted = reference.encrypt_block(b"password", &key);
+        assert_eq!(reference.subkeys(), &[0; 32]);
+
+        let engine = VncDesEngine::from_subkeys(VncDesEngine::dump_schedule(&key));
+        assert_eq!(engine.process_block(b"password"), encrypted);
+
+        reference.set_key(&key, false);
+        let decryptor = VncDesEngine::from_subkeys(*reference.subkeys());
+        assert_eq!(decryptor.process_block(&encrypted), *b"password");
+    }
+
+    #[cfg(feature = "fast-keyschedule")]
+    #[test]
+    fn test_fast_keyschedule_matches_reference() {
+        let mut key = [0x17u8, 0x52, 0x6b, 0x06, 0x23, 0x4e, 0x58, 0x07];
+        for round in 0..256u32 {
+            for encrypt in [true, false] {
+                assert_eq!(
+                    fast_keyschedule::raw_schedule(&key, encrypt),
+                    VncDesEngine::raw_schedule_reference(&key, encrypt),
+                    "key = {:02x?}",
+                    key
+                );
+            }
+            // 简单的伪随机序列覆盖各种密钥位组合
+            key = key.map(|b| b.wrapping_mul(31).wrapping_add(round as u8 ^ 0x5A));
+        }
+
+        for key in [[0x00; 8], [0xFF; 8]] {
+            assert_eq!(
+                fast_keyschedule::raw_schedule(&key, true),
+                VncDesEngine::raw_schedule_reference(&key, true)
+            );
+        }
+    }
 }
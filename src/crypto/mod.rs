@@ -2,9 +2,23 @@
 //!
 //! 提供符合VNC协议标准的DES加密/解密功能
 
+pub mod block_hex;
+pub mod challenge;
 pub mod des;
+pub(crate) mod scrub;
+#[cfg(feature = "std")]
 pub mod vnc_des;
 
 // 重新导出主要类型
+pub use block_hex::to_hex_array;
+pub use challenge::{
+    constant_time_eq, PreparedKey, VncAuth, VncChallengeProcessor, CHALLENGE_SIZE,
+};
 pub use des::VncDesEngine;
-pub use vnc_des::{PasswordProcessor, VncDesProcessor};
+#[cfg(feature = "smallvec")]
+pub use vnc_des::BlockBuf;
+#[cfg(feature = "std")]
+pub use vnc_des::{
+    BatchReport, PasswordProcessor, ProcessorBuilder, SecurityReport, VncDesProcessor,
+    SCHEME_VERSION,
+};
@@ -0,0 +1,20 @@
+//! 敏感数据清零
+//!
+//! 启用 `zeroize` 特性时使用 `zeroize` crate，否则使用volatile写入，
+//! 两种方式都能防止编译器将清零作为无用写入优化掉
+
+/// 覆写缓冲区中的敏感数据
+#[cfg(feature = "zeroize")]
+pub(crate) fn scrub<T: zeroize::DefaultIsZeroes>(buf: &mut [T]) {
+    zeroize::Zeroize::zeroize(buf);
+}
+
+/// 覆写缓冲区中的敏感数据
+#[cfg(not(feature = "zeroize"))]
+pub(crate) fn scrub<T: Copy + Default>(buf: &mut [T]) {
+    for item in buf.iter_mut() {
+        // SAFETY: item 来自可变借用，指针有效且对齐
+        unsafe { core::ptr::write_volatile(item, T::default()) };
+    }
+    core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
+}
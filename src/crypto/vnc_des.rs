@@ -2,15 +2,271 @@
 //!
 //! 提供易于使用的密码加密、解密和验证功能
 
-use crate::config::VncDesConfig;
+use crate::config::{
+    unpadded_byte_len, PasswordEncoding, SecurityWarning, VncDesConfig, VncDesConfigBuilder,
+};
+use crate::crypto::block_hex::to_hex_array;
+use crate::crypto::challenge::{constant_time_eq, VncAuth, CHALLENGE_SIZE};
 use crate::crypto::des::VncDesEngine;
+use crate::crypto::scrub::scrub;
+use crate::encoding::{Encoding, HexCase};
+use crate::encrypted_password::EncryptedPassword;
 use crate::error::{Result, VncDesError};
+use crate::key_provider::KeyProvider;
+use crate::progress::{NoProgress, Progress};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+/// 栈上存储的多块结果（最多2个块即16字节时不分配堆内存）
+#[cfg(feature = "smallvec")]
+pub type BlockBuf = smallvec::SmallVec<[u8; 16]>;
+
+/// 加密方案版本
+///
+/// 版本1即VNC标准路径：密码编码后截断/补0到8字节，以位序反转的密钥做单块DES-ECB。
+/// 该路径已冻结，相同的密钥、配置和密码在任何后续版本中都必须产生逐字节相同的密文；
+/// 若将来需要改变填充或编码等默认行为，会引入新的版本号而不是修改版本1。
+///
+/// 需要长期存储密文时，可使用 [`VncDesProcessor::encrypt_versioned`] 将版本号一并保存
+pub const SCHEME_VERSION: u32 = 1;
+
+/// 带版本标记的密文中版本号占用的字节数（大端序）
+const SCHEME_TAG_SIZE: usize = 4;
+
+/// 调试辅助：处理器在未调用 [`VncDesProcessor::clear`] 的情况下被销毁时记录警告
+///
+/// 仅在启用 `debug-internals` 特性时生效，用于提醒开发者显式清除密钥等敏感数据；
+/// 使用已知VNC产品公开默认密钥的处理器不会触发警告。未启用该特性时不存在此 `Drop` 实现
+#[cfg(feature = "debug-internals")]
+impl Drop for VncDesProcessor {
+    fn drop(&mut self) {
+        if self.holds_sensitive_state() {
+            log::warn!("VncDesProcessor 在未调用 clear() 的情况下被销毁，敏感状态可能残留在内存中");
+        }
+    }
+}
+
+/// 处理器构建器
+///
+/// 在一条链式调用中完成配置与处理器的创建，通过 [`VncDesProcessor::builder`] 获取
+#[derive(Debug, Default)]
+pub struct ProcessorBuilder {
+    config: VncDesConfigBuilder,
+    key_provider: Option<Arc<dyn KeyProvider>>,
+}
+
+impl ProcessorBuilder {
+    /// 使用已有配置作为起点
+    pub fn config(mut self, config: VncDesConfig) -> Self {
+        self.config = VncDesConfigBuilder::from(config);
+        self
+    }
+
+    /// 设置加密密钥
+    pub fn key(mut self, key: [u8; 8]) -> Self {
+        self.config = self.config.encryption_key(key);
+        self
+    }
+
+    /// 从十六进制字符串设置密钥
+    ///
+    /// 解析错误保留到 [`build`](Self::build) 返回（见 [`VncDesConfigBuilder::hex_key`]）
+    pub fn hex_key(mut self, hex_key: &str) -> Self {
+        self.config = self.config.hex_key(hex_key);
+        self
+    }
+
+    /// 从十六进制字符串设置密钥，解析失败时立即返回错误
+    pub fn try_hex_key(mut self, hex_key: &str) -> Result<Self> {
+        self.config = self.config.try_hex_key(hex_key)?;
+        Ok(self)
+    }
+
+    /// 设置严格模式
+    pub fn strict_mode(mut self, strict: bool) -> Self {
+        self.config = self.config.strict_mode(strict);
+        self
+    }
+
+    /// 设置自动截断
+    pub fn auto_truncate(mut self, truncate: bool) -> Self {
+        self.config = self.config.auto_truncate(truncate);
+        self
+    }
+
+    /// 设置最大密码长度
+    pub fn max_password_length(mut self, length: usize) -> Self {
+        self.config = self.config.max_password_length(length);
+        self
+    }
+
+    /// 设置最小密码长度
+    pub fn min_password_length(mut self, length: usize) -> Self {
+        self.config = self.config.min_password_length(length);
+        self
+    }
+
+    /// 设置密码编码方式
+    pub fn encoding(mut self, encoding: PasswordEncoding) -> Self {
+        self.config = self.config.password_encoding(encoding);
+        self
+    }
+
+    /// 设置密钥提供者（设置后配置中的密钥将被忽略）
+    pub fn key_provider(mut self, provider: impl KeyProvider + 'static) -> Self {
+        self.key_provider = Some(Arc::new(provider));
+        self
+    }
+
+    /// 验证配置并创建处理器
+    pub fn build(self) -> Result<VncDesProcessor> {
+        let mut processor = VncDesProcessor::new(self.config.build()?);
+        processor.key_provider = self.key_provider;
+        Ok(processor)
+    }
+}
+
+/// 批量加密报告
+///
+/// 启用 `serde` 特性时可序列化，密文以十六进制字符串表示
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BatchReport {
+    /// 与输入顺序一一对应的密文
+    #[cfg_attr(feature = "serde", serde(with = "hex_blocks_serde"))]
+    pub ciphertexts: Vec<Vec<u8>>,
+    /// 密文相同的输入索引分组（仅包含至少两个成员的分组，按首个索引排序）
+    pub reuse_clusters: Vec<Vec<usize>>,
+}
+
+/// [`BatchReport::ciphertexts`] 的序列化格式：十六进制字符串数组
+#[cfg(feature = "serde")]
+mod hex_blocks_serde {
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(blocks: &[Vec<u8>], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(blocks.iter().map(hex::encode))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<Vec<u8>>, D::Error> {
+        Vec::<String>::deserialize(deserializer)?
+            .iter()
+            .map(|block| hex::decode(block).map_err(de::Error::custom))
+            .collect()
+    }
+}
+
+impl BatchReport {
+    /// 是否存在密码复用
+    pub fn has_reuse(&self) -> bool {
+        !self.reuse_clusters.is_empty()
+    }
+}
+
+/// 安全评估报告
+///
+/// 由 [`VncDesProcessor::security_report`] 生成，描述当前有效配置的安全状况。
+/// 报告中不含密钥本身，启用 `serde` 特性时可序列化后上报。
+///
+/// `verify_password` 等验证方法在任何构建配置下都使用常量时间比较，因此报告中不再单独列出
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SecurityReport {
+    /// 有效密钥存在的安全问题
+    pub warnings: Vec<SecurityWarning>,
+    /// 密钥是否来自密钥提供者
+    pub key_from_provider: bool,
+    /// 密钥被当前配置拒绝（弱密钥检查、严格模式等）时的原因，此时加解密都会失败
+    pub key_rejected: Option<String>,
+    /// 相同明文是否总是得到相同密文（VNC DES无IV，始终为 `true`）
+    pub deterministic: bool,
+    /// 密钥熵估计（位）；公开或弱密钥为0，无法获取密钥时为 `None`
+    pub key_entropy_bits: Option<u32>,
+}
+
+impl SecurityReport {
+    /// 是否使用了公开的默认密钥
+    pub fn uses_default_key(&self) -> bool {
+        self.warnings.contains(&SecurityWarning::DefaultKey)
+    }
+}
+
+impl fmt::Display for SecurityReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let yes_no = |value: bool| if value { "是" } else { "否" };
+
+        writeln!(f, "🛡️ 安全评估报告")?;
+        writeln!(f, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")?;
+        writeln!(
+            f,
+            "密钥来源: {}",
+            if self.key_from_provider {
+                "密钥提供者"
+            } else {
+                "配置"
+            }
+        )?;
+        match self.key_entropy_bits {
+            Some(bits) => writeln!(f, "密钥熵估计: {} 位（DES有效密钥最多56位）", bits)?,
+            None => writeln!(f, "密钥熵估计: 未知（无法获取密钥）")?,
+        }
+        writeln!(f, "常量时间验证: 是（始终启用）")?;
+        writeln!(
+            f,
+            "确定性加密: {}（相同密码总是得到相同密文，可据此发现密码复用）",
+            yes_no(self.deterministic)
+        )?;
+
+        if let Some(reason) = &self.key_rejected {
+            writeln!(f, "❌ 密钥被当前配置拒绝: {}", reason)?;
+        }
+        if self.warnings.is_empty() && self.key_rejected.is_none() {
+            write!(f, "✅ 未发现密钥问题")
+        } else if self.warnings.is_empty() {
+            write!(f, "⚠️ 密钥不可用")
+        } else {
+            write!(f, "⚠️ 发现 {} 个问题:", self.warnings.len())?;
+            for warning in &self.warnings {
+                write!(f, "\n   - {}", warning)?;
+            }
+            Ok(())
+        }
+    }
+}
 
 /// VNC DES处理器
+///
+/// 加解密方法只需要 `&self`：每次操作使用临时的DES引擎完成密钥编排，用完即清零，
+/// 处理器本身只保存配置和密钥提供者。因此处理器是 `Send + Sync` 的，多线程服务器可以
+/// 通过 `Arc<VncDesProcessor>` 或 `&VncDesProcessor` 在工作线程之间共享，无需加锁或为每个线程克隆：
+///
+/// ```
+/// use std::sync::Arc;
+/// use std::thread;
+/// use vnc_des::VncDesProcessor;
+///
+/// let processor = Arc::new(VncDesProcessor::default());
+/// let workers: Vec<_> = (0..4)
+///     .map(|_| {
+///         let processor = Arc::clone(&processor);
+///         thread::spawn(move || processor.encrypt_password("password").unwrap())
+///     })
+///     .collect();
+///
+/// for worker in workers {
+///     assert_eq!(VncDesProcessor::to_hex_string(&worker.join().unwrap()), "dbd83cfd727a1458");
+/// }
+/// ```
 #[derive(Debug, Clone)]
 pub struct VncDesProcessor {
     config: VncDesConfig,
-    engine: VncDesEngine,
+    key_provider: Option<Arc<dyn KeyProvider>>,
+    /// 创建或 [`set_config`](Self::set_config) 时配置是否通过了RFC 6143严格模式的检查
+    config_valid: bool,
 }
 
 impl Default for VncDesProcessor {
@@ -20,14 +276,49 @@ impl Default for VncDesProcessor {
 }
 
 impl VncDesProcessor {
+    /// 创建处理器构建器
+    ///
+    /// ```
+    /// use vnc_des::{PasswordEncoding, StaticKeyProvider, VncDesProcessor};
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let processor = VncDesProcessor::builder()
+    ///     .hex_key("0123456789abcdef")
+    ///     .strict_mode(true)
+    ///     .auto_truncate(false)
+    ///     .encoding(PasswordEncoding::Utf8)
+    ///     .key_provider(StaticKeyProvider::new([0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef]))
+    ///     .build()?;
+    ///
+    /// let encrypted = processor.encrypt_password("password")?;
+    /// assert_eq!(processor.decrypt_password(&encrypted)?, "password");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn builder() -> ProcessorBuilder {
+        ProcessorBuilder::default()
+    }
+
     /// 使用指定配置创建处理器
     pub fn new(config: VncDesConfig) -> Self {
         Self {
+            config_valid: Self::check_config(&config),
             config,
-            engine: VncDesEngine::new(),
+            key_provider: None,
         }
     }
 
+    /// 使用指定配置创建处理器，并通过回调报告安全警告
+    ///
+    /// 对默认密钥、全0密钥、DES弱密钥等情况调用 `on_warning`，之后照常创建处理器。
+    /// 库本身不会向标准错误输出任何内容，由调用方决定如何记录警告。
+    pub fn new_with_warnings(config: VncDesConfig, on_warning: impl Fn(SecurityWarning)) -> Self {
+        for warning in config.warnings() {
+            on_warning(warning);
+        }
+        Self::new(config)
+    }
+
     /// 使用默认配置创建处理器
     pub fn with_default_config() -> Self {
         Self::default()
@@ -45,263 +336,2095 @@ impl VncDesProcessor {
         Ok(Self::new(config))
     }
 
+    /// 使用密钥提供者创建处理器
+    ///
+    /// 每次加密/解密时都会调用 `provider.key()` 获取密钥，
+    /// 配置中的 `encryption_key` 将被忽略
+    pub fn with_key_provider(provider: impl KeyProvider + 'static) -> Self {
+        Self {
+            config: VncDesConfig::default(),
+            key_provider: Some(Arc::new(provider)),
+            config_valid: true,
+        }
+    }
+
+    /// 生成当前有效配置的安全评估报告
+    ///
+    /// 警告基于原始密钥（提供者或配置中的密钥）生成，不经过弱密钥和严格模式检查，
+    /// 因此被配置拒绝的弱密钥同样会被报告；拒绝原因记录在 `key_rejected` 中
+    pub fn security_report(&self) -> SecurityReport {
+        let key = self.raw_key().ok();
+        let warnings = match key {
+            Some(key) => self.config.clone().with_key(key).warnings(),
+            None => Vec::new(),
+        };
+        // 公开密钥或弱密钥视为没有熵；否则为DES有效密钥长度（校验位不参与运算）
+        let key_entropy_bits = key.map(|_| if warnings.is_empty() { 56 } else { 0 });
+        let key_rejected = match key {
+            Some(_) => self.current_key().err().map(|e| e.to_string()),
+            None => None,
+        };
+
+        SecurityReport {
+            warnings,
+            key_from_provider: self.key_provider.is_some(),
+            key_rejected,
+            deterministic: true,
+            key_entropy_bits,
+        }
+    }
+
+    /// 获取当前有效密钥的子密钥表指纹
+    ///
+    /// 使用密钥提供者（如有）并应用校验位处理，见 [`VncDesEngine::schedule_fingerprint`]
+    pub fn key_schedule_fingerprint(&self) -> Result<String> {
+        Ok(VncDesEngine::schedule_fingerprint(&self.current_key()?))
+    }
+
     /// 获取当前配置
+    ///
+    /// 设置了密钥提供者时，加解密使用提供者返回的密钥，而不是配置中的
+    /// `encryption_key`（[`VncDesConfig::key_as_hex`] 等仍反映配置本身）
     pub fn config(&self) -> &VncDesConfig {
         &self.config
     }
 
     /// 更新配置
     pub fn set_config(&mut self, config: VncDesConfig) {
+        self.config_valid = Self::check_config(&config);
         self.config = config;
     }
 
+    /// RFC 6143严格模式下验证配置，只在创建处理器或更新配置时执行一次
+    fn check_config(config: &VncDesConfig) -> bool {
+        !config.rfc6143_strict || config.validate().is_ok()
+    }
+
+    /// 清除处理器持有的敏感状态
+    ///
+    /// 清零配置中的密钥并释放密钥提供者（子密钥表只存在于单次操作的临时引擎中，用完即清零）。
+    /// 清除后处理器不应再用于加解密。
+    pub fn clear(&mut self) {
+        scrub(&mut self.config.encryption_key);
+        self.key_provider = None;
+    }
+
+    /// 是否仍持有敏感状态（非公开的密钥或密钥提供者）
+    ///
+    /// 已知VNC产品的默认密钥是公开的，不视为敏感状态
+    #[cfg(any(test, feature = "debug-internals"))]
+    fn holds_sensitive_state(&self) -> bool {
+        let key = self.config.encryption_key;
+        let public = key == [0u8; 8]
+            || crate::config::KnownKey::ALL
+                .iter()
+                .any(|known| known.key() == key);
+        !public || self.key_provider.is_some()
+    }
+
+    /// 以处理器执行一次操作，随后清除其敏感状态
+    ///
+    /// 供库内部只使用一次的临时处理器使用，避免 `debug-internals` 的销毁警告指向库自身
+    pub(crate) fn use_once<T>(mut self, f: impl FnOnce(&Self) -> T) -> T {
+        let result = f(&self);
+        self.clear();
+        result
+    }
+
+    /// 创建单次操作使用的DES引擎（按配置拒绝弱密钥，销毁时清零子密钥表）
+    ///
+    /// 严格模式同样拒绝弱密钥，与 [`VncDesConfig::validate`] 的规则一致
+    fn engine(&self) -> VncDesEngine {
+        let mut engine = VncDesEngine::new();
+        engine.set_reject_weak_keys(self.config.reject_weak_keys || self.config.strict_mode);
+        engine
+    }
+
+    /// 获取未经任何检查的原始密钥（优先使用密钥提供者）
+    fn raw_key(&self) -> Result<[u8; 8]> {
+        match &self.key_provider {
+            Some(provider) => provider.key(),
+            None => Ok(self.config.encryption_key),
+        }
+    }
+
+    /// 获取本次操作使用的密钥（优先使用密钥提供者）
+    ///
+    /// 所有使用密钥的操作都经过这里：RFC 6143严格模式下配置未通过验证时直接返回验证错误，
+    /// 返回前会按照配置的 [`ParityMode`](crate::config::ParityMode) 处理校验位
+    fn current_key(&self) -> Result<[u8; 8]> {
+        // 配置已在创建时验证过，只有未通过时才再次验证以返回具体错误
+        if !self.config_valid {
+            self.config.validate()?;
+        }
+        let key = self.raw_key()?;
+        self.config.check_key(&key)?;
+        self.config.key_parity.apply(key)
+    }
+
     /// 处理密码（截断或验证长度）
-    fn process_password(&self, password: &str) -> Result<String> {
+    ///
+    /// 无需截断时直接借用输入，仅在截断时才产生新的字符串
+    fn process_password<'a>(&self, password: &'a str) -> Result<Cow<'a, str>> {
         if password.is_empty() {
             return Err(VncDesError::invalid_password_length("密码不能为空"));
         }
 
-        if password.len() > self.config.max_password_length {
+        if self.config.rfc6143_strict && !password.is_ascii() {
+            return Err(VncDesError::invalid_password_format(
+                "RFC 6143严格模式下密码只能包含ASCII字符",
+            ));
+        }
+
+        let encoded_len = self.config.password_encoding.encoded_len(password);
+        if encoded_len < self.config.min_password_length {
+            return Err(VncDesError::invalid_password_length(format!(
+                "密码长度 {} 字节，低于最小要求 {} 字节",
+                encoded_len, self.config.min_password_length
+            )));
+        }
+
+        if encoded_len > self.config.max_password_length {
             if self.config.strict_mode && !self.config.auto_truncate {
                 return Err(VncDesError::invalid_password_length(format!(
-                    "密码长度超过最大限制 {} 字符",
+                    "密码长度超过最大限制 {} 字节",
                     self.config.max_password_length
                 )));
             }
 
             if self.config.auto_truncate {
-                return Ok(password[..self.config.max_password_length].to_string());
+                log_debug!(
+                    "密码长度 {} 超过最大长度 {}，已截断",
+                    encoded_len,
+                    self.config.max_password_length
+                );
+                return Ok(Cow::Owned(
+                    self.config.truncate_password(password).to_string(),
+                ));
             }
         }
 
-        Ok(password.to_string())
+        Ok(Cow::Borrowed(password))
     }
 
-    /// 加密密码
-    pub fn encrypt_password(&mut self, password: &str) -> Result<Vec<u8>> {
+    /// 将密码处理、编码并填充为8字节明文块
+    ///
+    /// 截断或编码产生的中间副本在返回前会被清零，调用方负责清零返回的明文块
+    fn password_block(&self, password: &str) -> Result<[u8; 8]> {
         let processed_password = self.process_password(password)?;
+        let encoding = self.config.password_encoding;
+        // UTF-16下先按码元截断到8字节，避免在块边界拆开代理对导致无法解密
+        let block_password = match encoding {
+            PasswordEncoding::Utf16Le | PasswordEncoding::Utf16Be => {
+                encoding.truncate(&processed_password, 8)
+            }
+            _ => &processed_password,
+        };
+        let pwd_bytes = encoding.encode(block_password)?;
 
-        // 将密码转换为8字节数组，不足的用0填充
-        let mut password_bytes = [0u8; 8];
-        let pwd_bytes = processed_password.as_bytes();
+        // 将编码后的密码转换为8字节数组，超出的截断，不足的用填充字节（默认0）补足
+        let mut password_bytes = [self.config.pad_byte; 8];
         let copy_len = std::cmp::min(pwd_bytes.len(), 8);
+        if pwd_bytes.len() > 8 {
+            log_debug!(
+                "编码后的密码为 {} 字节，超出8字节的部分被截断",
+                pwd_bytes.len()
+            );
+        }
         password_bytes[..copy_len].copy_from_slice(&pwd_bytes[..copy_len]);
 
+        if let Cow::Owned(mut encoded) = pwd_bytes {
+            scrub(&mut encoded);
+        }
+        if let Cow::Owned(truncated) = processed_password {
+            scrub(&mut truncated.into_bytes());
+        }
+
+        Ok(password_bytes)
+    }
+
+    /// 加密密码
+    ///
+    /// 截断或编码产生的中间副本以及填充后的明文块在返回前会被清零
+    pub fn encrypt_password(&self, password: &str) -> Result<Vec<u8>> {
+        Ok(self.encrypt_password_array(password)?.to_vec())
+    }
+
+    /// 加密密码，返回长度由类型保证的 [`EncryptedPassword`]
+    pub fn encrypt(&self, password: &str) -> Result<EncryptedPassword> {
+        self.encrypt_password_array(password)
+            .map(EncryptedPassword::new)
+    }
+
+    /// 加密密码，直接返回8字节数组（不分配堆内存）
+    ///
+    /// 与 [`encrypt_password`](Self::encrypt_password) 结果相同，适合需要固定长度数组的调用方
+    ///
+    /// ```
+    /// use vnc_des::VncDesProcessor;
+    ///
+    /// let processor = VncDesProcessor::default();
+    /// let encrypted: [u8; 8] = processor.encrypt_password_array("password")?;
+    /// assert_eq!(VncDesProcessor::to_hex_string(&encrypted), "dbd83cfd727a1458");
+    /// # Ok::<(), vnc_des::VncDesError>(())
+    /// ```
+    pub fn encrypt_password_array(&self, password: &str) -> Result<[u8; 8]> {
+        let key = self.current_key()?;
+        let mut password_bytes = self.password_block(password)?;
+
         // 加密
         let mut encrypted = [0u8; 8];
-        self.engine
-            .encrypt(&mut encrypted, &password_bytes, &self.config.encryption_key)
-            .map_err(|e| VncDesError::encryption_failed(format!("加密失败: {}", e)))?;
+        let result = self.engine().encrypt(&mut encrypted, &password_bytes, &key);
+        scrub(&mut password_bytes);
 
-        Ok(encrypted.to_vec())
+        result.map_err(|e| VncDesError::encryption_failed(format!("加密失败: {}", e)))?;
+        Ok(encrypted)
     }
 
-    /// 解密密码
-    pub fn decrypt_password(&mut self, encrypted_password: &[u8]) -> Result<String> {
-        if encrypted_password.len() != 8 {
-            return Err(VncDesError::invalid_password_format(format!(
-                "加密密码长度必须为8字节，实际长度: {}",
-                encrypted_password.len()
-            )));
-        }
+    /// 惰性地逐个加密密码，产生16进制密文
+    ///
+    /// 所有密码共用一次密钥编排，适合批量转换大量密码（例如逐行读取的文件）。
+    /// 单个密码无效时对应位置产生错误，不影响后续密码；获取密钥失败（或密钥被
+    /// 弱密钥检查拒绝）时只产生一个错误，随后迭代结束。
+    ///
+    /// ```
+    /// use vnc_des::VncDesProcessor;
+    ///
+    /// let processor = VncDesProcessor::default();
+    /// let hex: Vec<String> = processor
+    ///     .encrypt_iter(["password", "secret"])
+    ///     .collect::<Result<_, _>>()?;
+    /// assert_eq!(hex[0], "dbd83cfd727a1458");
+    /// # Ok::<(), vnc_des::VncDesError>(())
+    /// ```
+    pub fn encrypt_iter<'a, I>(&'a self, passwords: I) -> impl Iterator<Item = Result<String>> + 'a
+    where
+        I: IntoIterator<Item = &'a str>,
+        I::IntoIter: 'a,
+    {
+        let mut engine = self.engine();
+        let setup = self
+            .current_key()
+            .and_then(|key| engine.try_deskey(&key, true))
+            .map_err(|e| VncDesError::encryption_failed(format!("加密失败: {}", e)));
 
-        let mut encrypted_array = [0u8; 8];
-        encrypted_array.copy_from_slice(encrypted_password);
+        let (mut engine, mut setup_error) = match setup {
+            Ok(()) => (Some(engine), None),
+            Err(e) => (None, Some(e)),
+        };
+        let mut passwords = passwords.into_iter();
+        let processor: &'a Self = self;
 
-        let mut decrypted = [0u8; 8];
-        self.engine
-            .decrypt(
-                &mut decrypted,
-                &encrypted_array,
-                &self.config.encryption_key,
-            )
-            .map_err(|e| VncDesError::decryption_failed(format!("解密失败: {}", e)))?;
+        std::iter::from_fn(move || {
+            if let Some(e) = setup_error.take() {
+                return Some(Err(e));
+            }
 
-        // 移除尾部的0字节并转换为字符串
-        let end_pos = decrypted.iter().position(|&x| x == 0).unwrap_or(8);
-        let password_str = std::str::from_utf8(&decrypted[..end_pos]).map_err(|e| {
-            VncDesError::decryption_failed(format!("解密结果不是有效的UTF-8: {}", e))
-        })?;
+            let Some(password) = passwords.next() else {
+                // 迭代结束后立即清零子密钥，而不必等到迭代器被销毁
+                engine = None;
+                return None;
+            };
+            let engine = engine.as_ref()?;
 
-        Ok(password_str.to_string())
+            Some(processor.password_block(password).map(|mut block| {
+                let mut encrypted = [0u8; 8];
+                engine.des(&block, &mut encrypted);
+                scrub(&mut block);
+                hex::encode(encrypted)
+            }))
+        })
     }
 
-    /// 验证密码
-    pub fn verify_password(
-        &mut self,
-        plain_password: &str,
-        encrypted_password: &[u8],
-    ) -> Result<bool> {
-        let encrypted_plain = self.encrypt_password(plain_password)?;
-        Ok(encrypted_plain == encrypted_password)
+    /// 加密一次，并按每种请求的编码格式输出结果
+    pub fn encrypt_encoded(
+        &self,
+        password: &str,
+        encodings: &[Encoding],
+    ) -> Result<HashMap<Encoding, String>> {
+        let encrypted = self.encrypt_password(password)?;
+        Ok(encodings
+            .iter()
+            .map(|&encoding| (encoding, encoding.encode(&encrypted)))
+            .collect())
     }
 
-    /// 将加密密码转换为十六进制字符串
-    pub fn to_hex_string(encrypted_password: &[u8]) -> String {
-        hex::encode(encrypted_password)
+    /// 批量加密密码
+    pub fn encrypt_batch(&self, passwords: &[&str]) -> Result<Vec<Vec<u8>>> {
+        self.encrypt_batch_with_progress(passwords, &NoProgress)
     }
 
-    /// 从十六进制字符串解析加密密码
-    pub fn from_hex_string(hex_string: &str) -> Result<Vec<u8>> {
-        let clean_hex = hex_string.trim().to_lowercase();
-        if clean_hex.len() != 16 {
-            return Err(VncDesError::hex_decode_error(format!(
-                "十六进制字符串长度必须为16字符，实际长度: {}",
-                clean_hex.len()
-            )));
+    /// 批量加密密码，每加密一项报告一次进度
+    pub fn encrypt_batch_with_progress(
+        &self,
+        passwords: &[&str],
+        progress: &impl Progress,
+    ) -> Result<Vec<Vec<u8>>> {
+        let total = Some(passwords.len());
+        passwords
+            .iter()
+            .enumerate()
+            .map(|(index, password)| {
+                let encrypted = self.encrypt_password(password)?;
+                progress.on_item(index + 1, total);
+                Ok(encrypted)
+            })
+            .collect()
+    }
+
+    /// 批量加密密码并报告密文相同的分组
+    ///
+    /// VNC DES是确定性的（固定密钥、无IV），相同的明文总会得到相同的密文，
+    /// 因此可用于发现密码复用。注意截断后相同的密码（如前8个字符相同）也会被归为一组。
+    pub fn encrypt_batch_report(&self, passwords: &[&str]) -> Result<BatchReport> {
+        let ciphertexts = self.encrypt_batch(passwords)?;
+
+        let mut groups: HashMap<&[u8], Vec<usize>> = HashMap::new();
+        for (index, ciphertext) in ciphertexts.iter().enumerate() {
+            groups.entry(ciphertext).or_default().push(index);
         }
 
-        hex::decode(&clean_hex)
-            .map_err(|e| VncDesError::hex_decode_error(format!("无法解析十六进制字符串: {}", e)))
-    }
+        let mut reuse_clusters: Vec<Vec<usize>> = groups
+            .into_values()
+            .filter(|indices| indices.len() > 1)
+            .collect();
+        reuse_clusters.sort();
 
-    /// 生成测试用的密码对（明文和加密后的十六进制）
-    pub fn generate_test_pair(&mut self, plain_password: &str) -> Result<(String, String)> {
-        let encrypted = self.encrypt_password(plain_password)?;
-        let hex_string = Self::to_hex_string(&encrypted);
-        Ok((plain_password.to_string(), hex_string))
+        Ok(BatchReport {
+            ciphertexts,
+            reuse_clusters,
+        })
     }
-}
 
-/// 密码处理器（无状态版本）
-pub struct PasswordProcessor;
+    /// 以ECB模式逐块加密或解密数据，结果追加到 `out`
+    fn crypt_blocks<E: Extend<u8>>(&self, data: &[u8], encrypt: bool, out: &mut E) -> Result<()> {
+        if data.len() % 8 != 0 {
+            return Err(VncDesError::invalid_password_format(format!(
+                "数据长度必须为8的倍数，实际长度: {}",
+                data.len()
+            )));
+        }
 
-impl PasswordProcessor {
-    /// 使用默认配置加密密码
-    pub fn encrypt_with_default(password: &str) -> Result<Vec<u8>> {
-        let mut processor = VncDesProcessor::default();
-        processor.encrypt_password(password)
+        self.crypt_ecb(data, encrypt, out)
     }
 
-    /// 使用默认配置解密密码
-    pub fn decrypt_with_default(encrypted_password: &[u8]) -> Result<String> {
-        let mut processor = VncDesProcessor::default();
-        processor.decrypt_password(encrypted_password)
+    /// 以ECB模式逐块处理长度为8的倍数的数据，不做模式相关的检查
+    fn crypt_ecb<E: Extend<u8>>(&self, data: &[u8], encrypt: bool, out: &mut E) -> Result<()> {
+        let key = self.current_key()?;
+        let mut engine = self.engine();
+        for chunk in data.chunks_exact(8) {
+            let mut block = [0u8; 8];
+            block.copy_from_slice(chunk);
+            let mut result = [0u8; 8];
+            if encrypt {
+                engine
+                    .encrypt(&mut result, &block, &key)
+                    .map_err(|e| VncDesError::encryption_failed(format!("加密失败: {}", e)))?;
+            } else {
+                engine
+                    .decrypt(&mut result, &block, &key)
+                    .map_err(|e| VncDesError::decryption_failed(format!("解密失败: {}", e)))?;
+            }
+            out.extend(result);
+        }
+        Ok(())
     }
 
-    /// 使用默认配置验证密码
-    pub fn verify_with_default(plain_password: &str, encrypted_password: &[u8]) -> Result<bool> {
-        let mut processor = VncDesProcessor::default();
-        processor.verify_password(plain_password, encrypted_password)
+    /// 以ECB模式加密多个8字节块（数据长度必须为8的倍数）
+    pub fn encrypt_blocks(&self, data: &[u8]) -> Result<Vec<u8>> {
+        let mut out = Vec::with_capacity(data.len());
+        self.crypt_blocks(data, true, &mut out)?;
+        Ok(out)
     }
 
-    /// 使用自定义密钥加密密码
-    pub fn encrypt_with_key(password: &str, key: &[u8; 8]) -> Result<Vec<u8>> {
-        let mut processor = VncDesProcessor::with_key(*key);
-        processor.encrypt_password(password)
+    /// 以ECB模式解密多个8字节块（数据长度必须为8的倍数）
+    pub fn decrypt_blocks(&self, data: &[u8]) -> Result<Vec<u8>> {
+        let mut out = Vec::with_capacity(data.len());
+        self.crypt_blocks(data, false, &mut out)?;
+        Ok(out)
     }
 
-    /// 使用自定义密钥解密密码
-    pub fn decrypt_with_key(encrypted_password: &[u8], key: &[u8; 8]) -> Result<String> {
-        let mut processor = VncDesProcessor::with_key(*key);
-        processor.decrypt_password(encrypted_password)
+    /// 与 [`encrypt_blocks`](Self::encrypt_blocks) 相同，但结果不超过2个块时存储在栈上
+    #[cfg(feature = "smallvec")]
+    pub fn encrypt_blocks_small(&self, data: &[u8]) -> Result<BlockBuf> {
+        let mut out = BlockBuf::new();
+        self.crypt_blocks(data, true, &mut out)?;
+        Ok(out)
     }
 
-    /// 使用自定义密钥验证密码
-    pub fn verify_with_key(
-        plain_password: &str,
-        encrypted_password: &[u8],
-        key: &[u8; 8],
-    ) -> Result<bool> {
-        let mut processor = VncDesProcessor::with_key(*key);
-        processor.verify_password(plain_password, encrypted_password)
+    /// 与 [`decrypt_blocks`](Self::decrypt_blocks) 相同，但结果不超过2个块时存储在栈上
+    #[cfg(feature = "smallvec")]
+    pub fn decrypt_blocks_small(&self, data: &[u8]) -> Result<BlockBuf> {
+        let mut out = BlockBuf::new();
+        self.crypt_blocks(data, false, &mut out)?;
+        Ok(out)
     }
 
-    /// 演示加密解密过程
-    pub fn demo_encryption(password: &str) -> Result<()> {
-        println!("🔐 VNC DES 密码加解密演示");
-        println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    /// 使用配置的密钥加密单个原始8字节块
+    ///
+    /// 不做任何密码相关的处理（截断、填充、长度检查），只执行VNC DES分组加密，
+    /// 适用于挑战-响应等直接处理固定块的场景。密钥来源、校验位处理和弱密钥检查
+    /// 与其他方法一致
+    ///
+    /// ```
+    /// use vnc_des::VncDesProcessor;
+    ///
+    /// let processor = VncDesProcessor::default();
+    /// let block = processor.encrypt_block(b"password")?;
+    /// assert_eq!(block.to_vec(), processor.encrypt_password("password")?);
+    /// assert_eq!(&processor.decrypt_block(&block)?, b"password");
+    /// # Ok::<(), vnc_des::VncDesError>(())
+    /// ```
+    pub fn encrypt_block(&self, block: &[u8; 8]) -> Result<[u8; 8]> {
+        let key = self.current_key()?;
+        let mut out = [0u8; 8];
+        self.engine()
+            .encrypt(&mut out, block, &key)
+            .map_err(|e| VncDesError::encryption_failed(format!("加密失败: {}", e)))?;
+        Ok(out)
+    }
 
-        let mut processor = VncDesProcessor::default();
+    /// 使用配置的密钥解密单个原始8字节块（见 [`encrypt_block`](Self::encrypt_block)）
+    pub fn decrypt_block(&self, block: &[u8; 8]) -> Result<[u8; 8]> {
+        let key = self.current_key()?;
+        let mut out = [0u8; 8];
+        self.engine()
+            .decrypt(&mut out, block, &key)
+            .map_err(|e| VncDesError::decryption_failed(format!("解密失败: {}", e)))?;
+        Ok(out)
+    }
 
-        // 显示配置信息
-        println!("🔧 配置信息:");
-        println!("   密钥: {}", processor.config().key_as_hex());
-        println!("   严格模式: {}", processor.config().strict_mode);
-        println!("   自动截断: {}", processor.config().auto_truncate);
-        println!(
-            "   最大密码长度: {}",
-            processor.config().max_password_length
-        );
-        println!();
+    /// 解密密码到调用方提供的缓冲区（不分配堆内存）
+    ///
+    /// 解密后的8字节写入 `out`，返回按字节去除填充（见 [`VncDesConfig::with_pad_byte`]）
+    /// 后的有效长度，如何解释这些字节由调用方决定
+    pub fn decrypt_into(&self, encrypted: &[u8; 8], out: &mut [u8; 8]) -> Result<usize> {
+        let key = self.current_key()?;
+        self.engine()
+            .decrypt(out, encrypted, &key)
+            .map_err(|e| VncDesError::decryption_failed(format!("解密失败: {}", e)))?;
 
-        // 加密
-        println!("📝 原始密码: '{}'", password);
-        let encrypted = processor.encrypt_password(password)?;
-        let hex_string = VncDesProcessor::to_hex_string(&encrypted);
-        println!("🔒 加密字节: {:?}", encrypted);
-        println!("🔤 十六进制: {}", hex_string);
-        println!();
+        Ok(unpadded_byte_len(out, self.config.pad_byte))
+    }
 
-        // 解密验证
-        let decrypted = processor.decrypt_password(&encrypted)?;
-        println!("🔓 解密结果: '{}'", decrypted);
+    /// 使用已保存的16进制加密密码响应VNC认证挑战
+    ///
+    /// 先用配置密钥解密保存的密码，再以解密得到的原始8字节作为DES密钥计算挑战响应，
+    /// 适用于"已有保存的密码，直接登录"的场景。中间的明文在返回前会被清零。
+    pub fn authenticate_from_stored(
+        &self,
+        stored_hex: &str,
+        challenge: &[u8; CHALLENGE_SIZE],
+    ) -> Result<[u8; CHALLENGE_SIZE]> {
+        let stored = Self::from_hex_string(stored_hex)?;
+        let stored: [u8; 8] = stored.as_slice().try_into().map_err(|_| {
+            VncDesError::invalid_password_format(format!(
+                "加密密码长度必须为8字节，实际长度: {}",
+                stored.len()
+            ))
+        })?;
 
-        // 验证
-        let is_valid = processor.verify_password(password, &encrypted)?;
-        println!("✅ 验证结果: {}", if is_valid { "匹配" } else { "不匹配" });
+        let mut password_key = [0u8; 8];
+        self.decrypt_into(&stored, &mut password_key)?;
+        let response = VncAuth::respond_with_key(&password_key, challenge);
+        scrub(&mut password_key);
 
-        println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-        Ok(())
+        Ok(response)
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::config::TIGHTVNC_DEFAULT_KEY;
-
-    #[test]
-    fn test_processor_creation() {
-        let processor = VncDesProcessor::default();
-        assert_eq!(processor.config().encryption_key, TIGHTVNC_DEFAULT_KEY);
+    /// 解密密码
+    ///
+    /// # 填充语义
+    ///
+    /// VNC将密码补0到8字节后加密，密文中不记录原始长度，因此解密时第一个0字节
+    /// （UTF-16下为第一个0码元）及其后的内容都视为填充：
+    ///
+    /// - 恰好8字节的密码没有填充，完整返回
+    /// - 密码本身包含的0字节与填充无法区分，0字节及其后的字符会丢失
+    ///
+    /// 已知原始长度时使用 [`decrypt_password_exact`](Self::decrypt_password_exact) 避免这种歧义。
+    /// 配置了非0的填充字节（见 [`VncDesConfig::with_pad_byte`]）时只去除末尾连续的填充字节。
+    pub fn decrypt_password(&self, encrypted_password: &[u8]) -> Result<String> {
+        self.decrypt(&EncryptedPassword::try_from(encrypted_password)?)
     }
 
-    #[test]
-    fn test_password_encryption_decryption() {
-        let mut processor = VncDesProcessor::default();
-        let password = "test123";
+    /// 按已知的原始长度解密密码（不按0字节截断）
+    ///
+    /// `len` 为密码按配置编码后的字节数（不超过8），解密结果的前 `len` 字节原样解码，
+    /// 其中的0字节保留为 `'\0'` 字符。
+    ///
+    /// ```
+    /// use vnc_des::VncDesProcessor;
+    ///
+    /// let processor = VncDesProcessor::default();
+    /// let encrypted = processor.encrypt_password("ab\0c")?;
+    /// assert_eq!(processor.decrypt_password(&encrypted)?, "ab");
+    /// assert_eq!(processor.decrypt_password_exact(&encrypted, 4)?, "ab\0c");
+    /// # Ok::<(), vnc_des::VncDesError>(())
+    /// ```
+    pub fn decrypt_password_exact(&self, encrypted_password: &[u8], len: usize) -> Result<String> {
+        if len > 8 {
+            return Err(VncDesError::invalid_password_length(format!(
+                "原始密码长度不能超过8字节，实际长度: {}",
+                len
+            )));
+        }
 
-        let encrypted = processor.encrypt_password(password).unwrap();
-        let decrypted = processor.decrypt_password(&encrypted).unwrap();
+        let encrypted = EncryptedPassword::try_from(encrypted_password)?;
+        let mut decrypted = [0u8; 8];
+        let result = self
+            .decrypt_into(encrypted.as_bytes(), &mut decrypted)
+            .and_then(|_| {
+                self.config
+                    .password_encoding
+                    .decode_exact(&decrypted[..len])
+            });
+        scrub(&mut decrypted);
 
-        assert_eq!(password, decrypted);
+        result
     }
 
-    #[test]
-    fn test_password_verification() {
-        let mut processor = VncDesProcessor::default();
-        let password = "password123";
+    /// 解密密码，返回去除填充后的原始字节（不做字符串解码）
+    ///
+    /// 早期VNC实现按字节（通常为Latin-1）保存密码，密钥错误时解密结果也是任意字节。
+    /// 此方法不校验UTF-8，如何转换为字符串由调用方决定。填充按配置的
+    /// [`PasswordEncoding`] 和填充字节去除。
+    ///
+    /// ```
+    /// use vnc_des::{PasswordEncoding, VncDesConfig, VncDesProcessor};
+    ///
+    /// // 以Latin-1保存的密码，按UTF-8解码会失败
+    /// let encrypted = VncDesProcessor::new(
+    ///     VncDesConfig::new().with_password_encoding(PasswordEncoding::Latin1),
+    /// )
+    /// .encrypt_password("pé")?;
+    ///
+    /// let processor = VncDesProcessor::default();
+    /// assert!(processor.decrypt_password(&encrypted).is_err());
+    /// let bytes = processor.decrypt_password_bytes(&encrypted)?;
+    /// assert_eq!(bytes, [b'p', 0xe9]);
+    /// assert_eq!(String::from_utf8_lossy(&bytes), "p\u{fffd}");
+    /// # Ok::<(), vnc_des::VncDesError>(())
+    /// ```
+    pub fn decrypt_password_bytes(&self, encrypted_password: &[u8]) -> Result<Vec<u8>> {
+        let encrypted = EncryptedPassword::try_from(encrypted_password)?;
+        let mut decrypted = [0u8; 8];
+        let result = self
+            .decrypt_into(encrypted.as_bytes(), &mut decrypted)
+            .map(|_| {
+                let len = self
+                    .config
+                    .password_encoding
+                    .unpadded_len_with(&decrypted, self.config.pad_byte);
+                decrypted[..len].to_vec()
+            });
+        scrub(&mut decrypted);
 
-        let encrypted = processor.encrypt_password(password).unwrap();
-        let is_valid = processor.verify_password(password, &encrypted).unwrap();
+        result
+    }
 
-        assert!(is_valid);
+    /// 解密长度由类型保证的 [`EncryptedPassword`]
+    ///
+    /// 基于 [`decrypt_password_bytes`](Self::decrypt_password_bytes)，按配置的编码严格还原字符串，
+    /// 结果不是有效编码时返回错误
+    pub fn decrypt(&self, encrypted_password: &EncryptedPassword) -> Result<String> {
+        let mut decrypted = self.decrypt_password_bytes(encrypted_password.as_bytes())?;
+        let result = self.config.password_encoding.decode_exact(&decrypted);
+        if result.is_err() {
+            log_debug!(
+                "解密结果（{} 字节）不是有效的{}",
+                decrypted.len(),
+                self.config.password_encoding
+            );
+        }
+        scrub(&mut decrypted);
 
-        let is_invalid = processor.verify_password("wrongpass", &encrypted).unwrap();
-        assert!(!is_invalid);
+        result
     }
 
-    #[test]
-    fn test_hex_conversion() {
-        let encrypted = vec![0x12, 0x34, 0x56, 0x78, 0x9A, 0xBC, 0xDE, 0xF0];
-        let hex_string = VncDesProcessor::to_hex_string(&encrypted);
-        assert_eq!(hex_string, "123456789abcdef0");
-
-        let decoded = VncDesProcessor::from_hex_string(&hex_string).unwrap();
-        assert_eq!(encrypted, decoded);
+    /// 获取处理器使用的加密方案版本（见 [`SCHEME_VERSION`]）
+    pub fn scheme_version(&self) -> u32 {
+        SCHEME_VERSION
     }
 
-    #[test]
-    fn test_password_processor() {
-        let password = "test";
-        let encrypted = PasswordProcessor::encrypt_with_default(password).unwrap();
-        let decrypted = PasswordProcessor::decrypt_with_default(&encrypted).unwrap();
-        assert_eq!(password, decrypted);
+    /// 加密密码，并在密文前附加4字节大端序的方案版本号
+    ///
+    /// 适用于长期存储：解密时通过 [`decrypt_versioned`](Self::decrypt_versioned)
+    /// 检查版本，避免用不兼容的方案静默解出错误结果
+    pub fn encrypt_versioned(&self, password: &str) -> Result<Vec<u8>> {
+        let encrypted = self.encrypt_password(password)?;
+
+        let mut tagged = Vec::with_capacity(SCHEME_TAG_SIZE + encrypted.len());
+        tagged.extend_from_slice(&self.scheme_version().to_be_bytes());
+        tagged.extend_from_slice(&encrypted);
+        Ok(tagged)
     }
 
-    #[test]
-    fn test_custom_key() {
+    /// 解密由 [`encrypt_versioned`](Self::encrypt_versioned) 生成的带版本标记的密文
+    ///
+    /// 版本号与当前方案不一致时返回 [`VncDesError::Unsupported`]
+    pub fn decrypt_versioned(&self, tagged: &[u8]) -> Result<String> {
+        if tagged.len() != SCHEME_TAG_SIZE + 8 {
+            return Err(VncDesError::invalid_password_format(format!(
+                "带版本标记的密文长度必须为{}字节，实际长度: {}",
+                SCHEME_TAG_SIZE + 8,
+                tagged.len()
+            )));
+        }
+
+        let (tag, encrypted) = tagged.split_at(SCHEME_TAG_SIZE);
+        let version = u32::from_be_bytes([tag[0], tag[1], tag[2], tag[3]]);
+        if version != self.scheme_version() {
+            return Err(VncDesError::unsupported(format!(
+                "密文方案版本 {} 与当前版本 {} 不一致",
+                version,
+                self.scheme_version()
+            )));
+        }
+
+        self.decrypt_password(encrypted)
+    }
+
+    /// 将任意长度的密码用填充字节（默认0）补足到8的倍数后逐块加密（ECB模式）
+    ///
+    /// 这不是RFC 6143的一部分：标准VNC认证只使用密码的前8字节，应使用
+    /// [`encrypt_password`](Self::encrypt_password)。此方法对应部分实现（如UltraVNC的扩展
+    /// 密码存储）将超长密码按8字节分块、每块独立DES加密的做法，结果长度为 `8 * ceil(len / 8)`。
+    ///
+    /// ECB模式下相同的明文块总是得到相同的密文块，会泄露密码中重复的8字节片段。
+    /// 密码不受 `max_password_length` 限制，也不会被截断；RFC 6143严格模式下不可用。
+    pub fn encrypt_password_blocks(&self, password: &str) -> Result<Vec<u8>> {
+        if self.config.rfc6143_strict {
+            return Err(VncDesError::invalid_password_length(
+                "RFC 6143严格模式下不支持多块密码",
+            ));
+        }
+        if password.is_empty() {
+            return Err(VncDesError::invalid_password_length("密码不能为空"));
+        }
+        let encoded_len = self.config.password_encoding.encoded_len(password);
+        if encoded_len < self.config.min_password_length {
+            return Err(VncDesError::invalid_password_length(format!(
+                "密码长度 {} 字节，低于最小要求 {} 字节",
+                encoded_len, self.config.min_password_length
+            )));
+        }
+
+        let encoded = self.config.password_encoding.encode(password)?;
+        let mut padded = vec![self.config.pad_byte; (encoded.len() + 7) / 8 * 8];
+        padded[..encoded.len()].copy_from_slice(&encoded);
+        if let Cow::Owned(mut encoded) = encoded {
+            scrub(&mut encoded);
+        }
+
+        let mut encrypted = Vec::with_capacity(padded.len());
+        let result = self.crypt_ecb(&padded, true, &mut encrypted);
+        scrub(&mut padded);
+
+        result?;
+        Ok(encrypted)
+    }
+
+    /// 解密由 [`encrypt_password_blocks`](Self::encrypt_password_blocks) 生成的多块密文
+    ///
+    /// 按配置的填充字节去除填充（见 [`PasswordEncoding::unpadded_len_with`]）
+    pub fn decrypt_password_blocks(&self, encrypted: &[u8]) -> Result<String> {
+        if encrypted.is_empty() || encrypted.len() % 8 != 0 {
+            return Err(VncDesError::invalid_password_format(format!(
+                "多块密文长度必须为8的正整数倍，实际长度: {}",
+                encrypted.len()
+            )));
+        }
+
+        let mut decrypted = Vec::with_capacity(encrypted.len());
+        let result = self
+            .crypt_ecb(encrypted, false, &mut decrypted)
+            .and_then(|_| {
+                let encoding = self.config.password_encoding;
+                let len = encoding.unpadded_len_with(&decrypted, self.config.pad_byte);
+                let result = encoding.decode_exact(&decrypted[..len]);
+                if result.is_err() {
+                    log_debug!("多块解密结果（{} 字节）不是有效的{}", len, encoding);
+                }
+                result
+            });
+        scrub(&mut decrypted);
+
+        result
+    }
+
+    /// 验证密码
+    ///
+    /// 使用常量时间比较密文，比较耗时与首个不同字节的位置无关
+    pub fn verify_password(&self, plain_password: &str, encrypted_password: &[u8]) -> Result<bool> {
+        let encrypted_plain = self.encrypt_password_array(plain_password)?;
+        Ok(constant_time_eq(&encrypted_plain, encrypted_password))
+    }
+
+    /// 验证密码与十六进制密文是否匹配（常量时间比较）
+    ///
+    /// 等价于先 [`from_hex_string`](Self::from_hex_string) 再 [`verify_password`](Self::verify_password)
+    ///
+    /// ```
+    /// use vnc_des::VncDesProcessor;
+    ///
+    /// let processor = VncDesProcessor::default();
+    /// assert!(processor.verify_hex("password", "dbd83cfd727a1458")?);
+    /// assert!(!processor.verify_hex("wrong", "dbd83cfd727a1458")?);
+    /// # Ok::<(), vnc_des::VncDesError>(())
+    /// ```
+    pub fn verify_hex(&self, plain_password: &str, hex_password: &str) -> Result<bool> {
+        let expected = Self::from_hex_string(hex_password)?;
+        self.verify_password(plain_password, &expected)
+    }
+
+    /// 不解密，判断两段密文是否对应同一个（填充后的）密码（常量时间比较）
+    ///
+    /// VNC DES 使用固定密钥且没有IV，相同的明文块总是得到相同的密文，可用于存量密码的
+    /// 去重和审计。结果只在两段密文由**同一密钥**加密时才有意义：不同密钥下的密文
+    /// 即使对应相同密码也几乎不会相等。长度不同时返回 `false`
+    ///
+    /// ```
+    /// use vnc_des::VncDesProcessor;
+    ///
+    /// let a = VncDesProcessor::from_hex_string("dbd83cfd727a1458")?;
+    /// let b = VncDesProcessor::from_hex_string("DB:D8:3C:FD:72:7A:14:58")?;
+    /// assert!(VncDesProcessor::ciphertext_equal(&a, &b));
+    /// # Ok::<(), vnc_des::VncDesError>(())
+    /// ```
+    pub fn ciphertext_equal(a: &[u8], b: &[u8]) -> bool {
+        constant_time_eq(a, b)
+    }
+
+    /// 验证密码（常量时间比较密文）
+    ///
+    /// [`verify_password`](Self::verify_password) 本身已使用常量时间比较，此方法保留以兼容旧代码
+    pub fn verify_password_constant_time(
+        &self,
+        plain_password: &str,
+        encrypted_password: &[u8],
+    ) -> Result<bool> {
+        self.verify_password(plain_password, encrypted_password)
+    }
+
+    /// 将加密密码转换为十六进制字符串
+    ///
+    /// 8字节输入通过 [`to_hex_array`] 编码；不需要 `String` 时可直接使用该函数避免分配
+    pub fn to_hex_string(encrypted_password: &[u8]) -> String {
+        match <&[u8; 8]>::try_from(encrypted_password) {
+            Ok(block) => to_hex_array(block).iter().map(|&b| char::from(b)).collect(),
+            Err(_) => hex::encode(encrypted_password),
+        }
+    }
+
+    /// 将加密密码转换为大写十六进制字符串
+    pub fn to_hex_string_upper(encrypted_password: &[u8]) -> String {
+        Self::to_hex_string_with_case(encrypted_password, HexCase::Upper)
+    }
+
+    /// 按指定大小写将加密密码转换为十六进制字符串
+    pub fn to_hex_string_with_case(encrypted_password: &[u8], case: HexCase) -> String {
+        case.encode(encrypted_password)
+    }
+
+    /// 从十六进制字符串解析加密密码
+    ///
+    /// 先按 [`normalize_hex_string`](Self::normalize_hex_string) 去除 `0x` 前缀和分隔符，
+    /// 因此 `0x33483fd570cf869b`、`33:48:3f:d5:70:cf:86:9b` 等调试器或抓包工具中的格式也可直接使用。
+    ///
+    /// 规范化后不是16个字符时返回 [`VncDesError::InvalidHexLength`]，
+    /// 包含非十六进制字符时返回 [`VncDesError::HexDecodeError`]
+    ///
+    /// ```
+    /// use vnc_des::{VncDesError, VncDesProcessor};
+    ///
+    /// assert!(matches!(
+    ///     VncDesProcessor::from_hex_string("dbd83cfd727a14"),
+    ///     Err(VncDesError::InvalidHexLength { expected: 16, actual: 14 })
+    /// ));
+    /// assert!(matches!(
+    ///     VncDesProcessor::from_hex_string("dbd83cfd727a145z"),
+    ///     Err(VncDesError::HexDecodeError(_))
+    /// ));
+    /// ```
+    pub fn from_hex_string(hex_string: &str) -> Result<Vec<u8>> {
+        let clean_hex = Self::normalize_hex_string(hex_string);
+        let len = clean_hex.chars().count();
+        if len != 16 {
+            return Err(VncDesError::invalid_hex_length(16, len));
+        }
+
+        hex::decode(&clean_hex)
+            .map_err(|e| VncDesError::hex_decode_error(format!("无法解析十六进制字符串: {}", e)))
+    }
+
+    /// 将加密密码转换为Base64字符串（标准字母表，带填充）
+    pub fn to_base64_string(encrypted_password: &[u8]) -> String {
+        Encoding::Base64.encode(encrypted_password)
+    }
+
+    /// 从Base64字符串解析加密密码（标准字母表，带填充）
+    pub fn from_base64_string(base64_string: &str) -> Result<Vec<u8>> {
+        let decoded = Encoding::Base64.decode(base64_string)?;
+        if decoded.len() != 8 {
+            return Err(VncDesError::base64_decode_error(format!(
+                "Base64解码后长度必须为8字节，实际长度: {}",
+                decoded.len()
+            )));
+        }
+
+        Ok(decoded)
+    }
+
+    /// 规范化十六进制字符串：去除首尾空白和 `0x`/`0X` 前缀，移除 `:`、`-` 和空白分隔符，转为小写
+    ///
+    /// 只做格式清理，不检查长度和字符是否有效
+    pub fn normalize_hex_string(hex_string: &str) -> String {
+        let trimmed = hex_string.trim();
+        let unprefixed = trimmed
+            .strip_prefix("0x")
+            .or_else(|| trimmed.strip_prefix("0X"))
+            .unwrap_or(trimmed);
+
+        unprefixed
+            .chars()
+            .filter(|c| !matches!(c, ':' | '-') && !c.is_whitespace())
+            .collect::<String>()
+            .to_lowercase()
+    }
+
+    /// 从Windows注册表导出（`.reg` 文件）的 `hex:` 字节列表解析加密密码
+    ///
+    /// TightVNC/UltraVNC在注册表中以 `REG_BINARY` 保存密码，导出格式为
+    /// `"Password"=hex:33,48,3f,d5,70,cf,86,9b`。接受完整的一行、`hex:` 开头的值或仅逗号分隔的字节，
+    /// 忽略空白和 `\` 续行。字节数必须为8。
+    ///
+    /// ```
+    /// use vnc_des::VncDesProcessor;
+    ///
+    /// let encrypted = VncDesProcessor::from_reg_hex(r#""Password"=hex:db,d8,3c,fd,\
+    ///   72,7a,14,58"#)?;
+    /// assert_eq!(VncDesProcessor::to_hex_string(&encrypted), "dbd83cfd727a1458");
+    /// # Ok::<(), vnc_des::VncDesError>(())
+    /// ```
+    pub fn from_reg_hex(reg_value: &str) -> Result<Vec<u8>> {
+        // 值部分位于最后一个 `=` 之后（值名称本身带引号，字节列表中不会出现 `=`）
+        let value = reg_value
+            .rsplit_once('=')
+            .map_or(reg_value, |(_, value)| value)
+            .trim();
+        let value = match value.get(..4) {
+            Some(prefix) if prefix.eq_ignore_ascii_case("hex:") => &value[4..],
+            _ => value,
+        };
+
+        let bytes = value
+            .split(',')
+            .map(|byte| byte.trim_matches(|c: char| c == '\\' || c.is_whitespace()))
+            .filter(|byte| !byte.is_empty())
+            .map(|byte| {
+                if byte.len() > 2 {
+                    return Err(VncDesError::hex_decode_error(format!(
+                        "注册表字节 '{}' 不是1到2位十六进制数",
+                        byte
+                    )));
+                }
+                u8::from_str_radix(byte, 16).map_err(|_| {
+                    VncDesError::hex_decode_error(format!("无法解析注册表字节 '{}'", byte))
+                })
+            })
+            .collect::<Result<Vec<u8>>>()?;
+
+        if bytes.len() != 8 {
+            return Err(VncDesError::invalid_password_format(format!(
+                "注册表中的加密密码必须为8字节，实际长度: {}",
+                bytes.len()
+            )));
+        }
+
+        Ok(bytes)
+    }
+
+    /// 将单个8字节密文从当前密钥轮换到新密钥
+    ///
+    /// 直接对填充后的明文块重新加密，中间明文在返回前被清零
+    pub fn rotate_key(&self, encrypted: &[u8; 8], new_key: &[u8; 8]) -> Result<[u8; 8]> {
+        let key = self.current_key()?;
+        let mut plain = [0u8; 8];
+        let mut rotated = [0u8; 8];
+
+        let mut engine = self.engine();
+        let result = engine
+            .decrypt(&mut plain, encrypted, &key)
+            .and_then(|_| engine.encrypt(&mut rotated, &plain, new_key));
+        scrub(&mut plain);
+
+        result.map_err(|e| VncDesError::encryption_failed(format!("密钥轮换失败: {}", e)))?;
+        Ok(rotated)
+    }
+
+    /// 将JSON对象数组中指定字段的十六进制密文从当前密钥轮换到新密钥
+    ///
+    /// 任一元素不是对象、缺少该字段或字段不是有效密文时返回错误，且不修改任何元素。
+    /// 返回轮换的条目数。
+    #[cfg(feature = "serde")]
+    pub fn rotate_json_value(
+        &self,
+        value: &mut serde_json::Value,
+        new_key: &[u8; 8],
+        password_field: &str,
+    ) -> Result<usize> {
+        let entries = value
+            .as_array_mut()
+            .ok_or_else(|| VncDesError::invalid_password_format("JSON顶层必须是对象数组"))?;
+
+        // 先全部计算，全部成功后再写回
+        let mut rotated = Vec::with_capacity(entries.len());
+        for (index, entry) in entries.iter().enumerate() {
+            let hex_password = entry
+                .get(password_field)
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| {
+                    VncDesError::invalid_password_format(format!(
+                        "第 {} 个条目缺少字符串字段 '{}'",
+                        index, password_field
+                    ))
+                })?;
+
+            let encrypted = Self::from_hex_string(hex_password)?;
+            let mut block = [0u8; 8];
+            block.copy_from_slice(&encrypted);
+            rotated.push(self.rotate_key(&block, new_key)?);
+        }
+
+        for (entry, block) in entries.iter_mut().zip(&rotated) {
+            entry[password_field] = serde_json::Value::String(Self::to_hex_string(block));
+        }
+
+        Ok(rotated.len())
+    }
+
+    /// 轮换JSON凭据文件中所有条目的加密密码，并写回文件
+    ///
+    /// 文件内容应为对象数组，`password_field` 字段保存十六进制密文。
+    /// 新内容先写入同一目录下的临时文件，再重命名覆盖原文件，写入失败时原文件保持不变。
+    /// 返回轮换的条目数。
+    #[cfg(feature = "serde")]
+    pub fn rotate_json_file<P: AsRef<std::path::Path>>(
+        &self,
+        path: P,
+        new_key: &[u8; 8],
+        password_field: &str,
+    ) -> Result<usize> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)?;
+        let mut value: serde_json::Value = serde_json::from_str(&content)?;
+
+        let count = self.rotate_json_value(&mut value, new_key, password_field)?;
+        let content = serde_json::to_string_pretty(&value)?;
+
+        // 先完整写入同一目录下的临时文件再重命名覆盖，中途失败不会破坏原有凭据
+        let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+        let temp_path = path.with_file_name(format!(".{}.{}.tmp", file_name, std::process::id()));
+        let written = std::fs::File::create(&temp_path)
+            .and_then(|mut file| {
+                use std::io::Write;
+                file.write_all(content.as_bytes())?;
+                file.sync_all()
+            })
+            .and_then(|()| {
+                // 保留原文件的权限（凭据文件通常只有所有者可读）
+                let permissions = std::fs::metadata(path)?.permissions();
+                std::fs::set_permissions(&temp_path, permissions)
+            })
+            .and_then(|()| std::fs::rename(&temp_path, path));
+        if written.is_err() {
+            let _ = std::fs::remove_file(&temp_path);
+        }
+        written?;
+
+        Ok(count)
+    }
+
+    /// 检查十六进制字符串是否为格式正确的8字节密文（不执行解密）
+    pub fn is_valid_hex(hex_string: &str) -> bool {
+        Self::from_hex_string(hex_string).is_ok()
+    }
+
+    /// 生成测试用的密码对（明文和加密后的十六进制）
+    pub fn generate_test_pair(&self, plain_password: &str) -> Result<(String, String)> {
+        let encrypted = self.encrypt_password(plain_password)?;
+        let hex_string = Self::to_hex_string(&encrypted);
+        Ok((plain_password.to_string(), hex_string))
+    }
+}
+
+/// `*_with_default` 共用的默认处理器
+///
+/// 处理器只保存默认配置（公开的默认密钥），子密钥表只存在于每次操作的临时引擎中，
+/// 因此所有线程可以直接共享同一个实例
+static DEFAULT_PROCESSOR: VncDesProcessor = VncDesProcessor {
+    config: VncDesConfig::DEFAULT,
+    key_provider: None,
+    config_valid: true,
+};
+
+/// 密码处理器（无状态版本）
+///
+/// `*_with_default` 系列函数共用一个全局的默认处理器；`*_with_key` 系列每次创建新的处理器，
+/// 在循环中使用同一自定义密钥时应直接持有一个 [`VncDesProcessor`]
+pub struct PasswordProcessor;
+
+impl PasswordProcessor {
+    /// 使用默认配置加密密码
+    pub fn encrypt_with_default(password: &str) -> Result<Vec<u8>> {
+        DEFAULT_PROCESSOR.encrypt_password(password)
+    }
+
+    /// 使用默认配置解密密码
+    pub fn decrypt_with_default(encrypted_password: &[u8]) -> Result<String> {
+        DEFAULT_PROCESSOR.decrypt_password(encrypted_password)
+    }
+
+    /// 使用默认配置验证密码
+    pub fn verify_with_default(plain_password: &str, encrypted_password: &[u8]) -> Result<bool> {
+        DEFAULT_PROCESSOR.verify_password(plain_password, encrypted_password)
+    }
+
+    /// 使用默认配置验证密码与十六进制密文是否匹配（常量时间比较）
+    ///
+    /// ```rust
+    /// use vnc_des::PasswordProcessor;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// assert!(PasswordProcessor::verify_hex_with_default("test", "2f981dc548e09ec2")?);
+    /// assert!(!PasswordProcessor::verify_hex_with_default("wrong", "2f981dc548e09ec2")?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn verify_hex_with_default(plain_password: &str, hex_password: &str) -> Result<bool> {
+        DEFAULT_PROCESSOR.verify_hex(plain_password, hex_password)
+    }
+
+    /// 使用自定义密钥加密密码
+    pub fn encrypt_with_key(password: &str, key: &[u8; 8]) -> Result<Vec<u8>> {
+        VncDesProcessor::with_key(*key).use_once(|processor| processor.encrypt_password(password))
+    }
+
+    /// 使用自定义密钥解密密码
+    pub fn decrypt_with_key(encrypted_password: &[u8], key: &[u8; 8]) -> Result<String> {
+        VncDesProcessor::with_key(*key)
+            .use_once(|processor| processor.decrypt_password(encrypted_password))
+    }
+
+    /// 使用自定义密钥验证密码
+    pub fn verify_with_key(
+        plain_password: &str,
+        encrypted_password: &[u8],
+        key: &[u8; 8],
+    ) -> Result<bool> {
+        VncDesProcessor::with_key(*key)
+            .use_once(|processor| processor.verify_password(plain_password, encrypted_password))
+    }
+
+    /// 使用自定义密钥验证密码与十六进制密文是否匹配（常量时间比较）
+    pub fn verify_hex_with_key(
+        plain_password: &str,
+        hex_password: &str,
+        key: &[u8; 8],
+    ) -> Result<bool> {
+        VncDesProcessor::with_key(*key)
+            .use_once(|processor| processor.verify_hex(plain_password, hex_password))
+    }
+
+    /// 演示加密解密过程
+    pub fn demo_encryption(password: &str) -> Result<()> {
+        println!("🔐 VNC DES 密码加解密演示");
+        println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+
+        let processor = VncDesProcessor::default();
+
+        // 显示配置信息
+        println!("🔧 配置信息:");
+        println!("   密钥: {}", processor.config().key_as_hex());
+        println!("   严格模式: {}", processor.config().strict_mode);
+        println!("   自动截断: {}", processor.config().auto_truncate);
+        println!(
+            "   最大密码长度: {}",
+            processor.config().max_password_length
+        );
+        println!();
+
+        // 加密
+        println!("📝 原始密码: '{}'", password);
+        let encrypted = processor.encrypt_password(password)?;
+        let hex_string = VncDesProcessor::to_hex_string(&encrypted);
+        println!("🔒 加密字节: {:?}", encrypted);
+        println!("🔤 十六进制: {}", hex_string);
+        println!();
+
+        // 解密验证
+        let decrypted = processor.decrypt_password(&encrypted)?;
+        println!("🔓 解密结果: '{}'", decrypted);
+
+        // 验证
+        let is_valid = processor.verify_password(password, &encrypted)?;
+        println!("✅ 验证结果: {}", if is_valid { "匹配" } else { "不匹配" });
+
+        println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{ParityMode, PasswordEncoding, TIGHTVNC_DEFAULT_KEY};
+
+    #[test]
+    fn test_processor_creation() {
+        let processor = VncDesProcessor::default();
+        assert_eq!(processor.config().encryption_key, TIGHTVNC_DEFAULT_KEY);
+    }
+
+    #[test]
+    fn test_shared_across_threads() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<VncDesProcessor>();
+
+        let processor = VncDesProcessor::default();
+        let expected = processor.encrypt_password("password").unwrap();
+        std::thread::scope(|scope| {
+            for _ in 0..4 {
+                scope.spawn(|| {
+                    for _ in 0..100 {
+                        let encrypted = processor.encrypt_password("password").unwrap();
+                        assert_eq!(encrypted, expected);
+                        assert_eq!(processor.decrypt_password(&encrypted).unwrap(), "password");
+                    }
+                });
+            }
+        });
+    }
+
+    #[test]
+    fn test_cached_default_processor() {
+        for _ in 0..3 {
+            let encrypted = PasswordProcessor::encrypt_with_default("password").unwrap();
+            assert_eq!(
+                VncDesProcessor::to_hex_string(&encrypted),
+                "dbd83cfd727a1458"
+            );
+            assert_eq!(
+                PasswordProcessor::decrypt_with_default(&encrypted).unwrap(),
+                "password"
+            );
+            assert!(PasswordProcessor::verify_with_default("password", &encrypted).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_new_with_warnings() {
+        use std::cell::RefCell;
+
+        let warnings = RefCell::new(Vec::new());
+        let processor = VncDesProcessor::new_with_warnings(VncDesConfig::default(), |w| {
+            warnings.borrow_mut().push(w)
+        });
+        assert_eq!(processor.config().encryption_key, TIGHTVNC_DEFAULT_KEY);
+        assert_eq!(*warnings.borrow(), vec![SecurityWarning::DefaultKey]);
+
+        warnings.borrow_mut().clear();
+        VncDesProcessor::new_with_warnings(VncDesConfig::new().with_key([0u8; 8]), |w| {
+            warnings.borrow_mut().push(w)
+        });
+        assert_eq!(*warnings.borrow(), vec![SecurityWarning::AllZeroKey]);
+
+        warnings.borrow_mut().clear();
+        VncDesProcessor::new_with_warnings(VncDesConfig::new().with_key([0x80; 8]), |w| {
+            warnings.borrow_mut().push(w)
+        });
+        assert_eq!(*warnings.borrow(), vec![SecurityWarning::WeakKey]);
+
+        warnings.borrow_mut().clear();
+        VncDesProcessor::new_with_warnings(
+            VncDesConfig::new().with_key([1, 2, 3, 4, 5, 6, 7, 8]),
+            |w| warnings.borrow_mut().push(w),
+        );
+        assert!(warnings.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_password_encryption_decryption() {
+        let processor = VncDesProcessor::default();
+        let password = "test123";
+
+        let encrypted = processor.encrypt_password(password).unwrap();
+        let decrypted = processor.decrypt_password(&encrypted).unwrap();
+
+        assert_eq!(password, decrypted);
+        assert_eq!(
+            processor
+                .encrypt_password_array(password)
+                .unwrap()
+                .as_slice(),
+            encrypted.as_slice()
+        );
+    }
+
+    #[test]
+    fn test_password_verification() {
+        let processor = VncDesProcessor::default();
+        let password = "password123";
+
+        let encrypted = processor.encrypt_password(password).unwrap();
+        let is_valid = processor.verify_password(password, &encrypted).unwrap();
+
+        assert!(is_valid);
+
+        let is_invalid = processor.verify_password("wrongpass", &encrypted).unwrap();
+        assert!(!is_invalid);
+    }
+
+    #[test]
+    fn test_hex_conversion() {
+        let encrypted = vec![0x12, 0x34, 0x56, 0x78, 0x9A, 0xBC, 0xDE, 0xF0];
+        let hex_string = VncDesProcessor::to_hex_string(&encrypted);
+        assert_eq!(hex_string, "123456789abcdef0");
+
+        let decoded = VncDesProcessor::from_hex_string(&hex_string).unwrap();
+        assert_eq!(encrypted, decoded);
+
+        // 调试器和抓包工具中常见的格式
+        for input in [
+            "0x123456789abcdef0",
+            "0X123456789ABCDEF0",
+            "12:34:56:78:9a:bc:de:f0",
+            "12-34-56-78-9A-BC-DE-F0",
+            " 12 34 56 78 9a bc de f0\n",
+        ] {
+            assert_eq!(
+                VncDesProcessor::from_hex_string(input).unwrap(),
+                encrypted,
+                "{}",
+                input
+            );
+        }
+
+        // 清理后长度不对或含无效字符仍然报错
+        for input in [
+            "0x123456789abcdef",
+            "12:34:56:78:9a:bc:de",
+            "12.34.56.78.9a.bc.de.f0",
+            "0x0x123456789abcdef0",
+        ] {
+            assert!(
+                VncDesProcessor::from_hex_string(input).is_err(),
+                "{}",
+                input
+            );
+        }
+
+        let upper = VncDesProcessor::to_hex_string_upper(&encrypted);
+        assert_eq!(upper, "123456789ABCDEF0");
+        assert_eq!(
+            VncDesProcessor::to_hex_string_with_case(&encrypted, HexCase::Lower),
+            hex_string
+        );
+        assert_eq!(VncDesProcessor::from_hex_string(&upper).unwrap(), encrypted);
+    }
+
+    #[test]
+    fn test_base64_conversion() {
+        let processor = VncDesProcessor::default();
+        let encrypted = processor.encrypt_password("password").unwrap();
+
+        let base64_string = VncDesProcessor::to_base64_string(&encrypted);
+        assert_eq!(base64_string, "29g8/XJ6FFg=");
+        assert_eq!(
+            VncDesProcessor::from_base64_string(&base64_string).unwrap(),
+            encrypted
+        );
+
+        // 长度不是8字节的密文被拒绝
+        assert!(matches!(
+            VncDesProcessor::from_base64_string("AAAA"),
+            Err(VncDesError::Base64DecodeError(_))
+        ));
+    }
+
+    #[test]
+    fn test_decrypt_into() {
+        let processor = VncDesProcessor::default();
+
+        for password in ["test", "password", "a"] {
+            let encrypted = processor.encrypt_password(password).unwrap();
+            let encrypted: [u8; 8] = encrypted.as_slice().try_into().unwrap();
+
+            let mut out = [0xffu8; 8];
+            let len = processor.decrypt_into(&encrypted, &mut out).unwrap();
+            let decrypted = processor.decrypt_password(&encrypted).unwrap();
+
+            assert_eq!(len, password.len());
+            assert_eq!(&out[..len], decrypted.as_bytes());
+        }
+    }
+
+    #[test]
+    fn test_encrypt_blocks() {
+        let processor = VncDesProcessor::default();
+        let data = *b"password12345678";
+
+        let encrypted = processor.encrypt_blocks(&data).unwrap();
+        assert_eq!(encrypted.len(), 16);
+        assert_eq!(
+            encrypted[..8],
+            processor.encrypt_password("password").unwrap()[..]
+        );
+        assert_eq!(processor.decrypt_blocks(&encrypted).unwrap(), data);
+
+        assert!(processor.encrypt_blocks(&data[..12]).is_err());
+        assert!(processor.encrypt_blocks(&[]).unwrap().is_empty());
+
+        #[cfg(feature = "smallvec")]
+        {
+            let small = processor.encrypt_blocks_small(&data).unwrap();
+            assert!(!small.spilled());
+            assert_eq!(small[..], encrypted[..]);
+            assert_eq!(processor.decrypt_blocks_small(&small).unwrap()[..], data);
+        }
+    }
+
+    #[test]
+    fn test_rfc6143_strict_operations() {
+        let processor = VncDesProcessor::new(VncDesConfig::new().with_rfc6143_strict(true));
+
+        assert!(processor.encrypt_password("password").is_ok());
+        assert!(processor.encrypt_password("pässword").is_err());
+
+        // 通用的ECB接口不受挑战长度限制，挑战接口的长度由类型保证
+        assert!(processor.encrypt_blocks(&[0u8; 16]).is_ok());
+        assert!(processor.encrypt_blocks(&[0u8; 8]).is_ok());
+        assert!(processor.decrypt_blocks(&[0u8; 24]).is_ok());
+        assert!(processor
+            .authenticate_from_stored("dbd83cfd727a1458", &[0u8; 16])
+            .is_ok());
+
+        // 处理时同样执行配置检查
+        let config = VncDesConfig::new()
+            .with_rfc6143_strict(true)
+            .with_key([1, 2, 3, 4, 5, 6, 7, 8]);
+        let processor = VncDesProcessor::new(config.clone());
+        assert!(!processor.config_valid);
+        assert!(matches!(
+            processor.encrypt_password("password"),
+            Err(VncDesError::ConfigError(_))
+        ));
+
+        // 更新配置时重新验证
+        let mut processor = VncDesProcessor::default();
+        assert!(processor.config_valid);
+        processor.set_config(config);
+        assert!(!processor.config_valid);
+        processor.set_config(VncDesConfig::new().with_rfc6143_strict(true));
+        assert!(processor.config_valid);
+        assert!(processor.encrypt_password("password").is_ok());
+    }
+
+    #[test]
+    fn test_rfc6143_strict_rejects_every_operation() {
+        let encrypted = VncDesProcessor::default().encrypt("pw").unwrap();
+        let block = *encrypted.as_bytes();
+
+        // 非0填充字节不符合规范：解密和原始块接口同样拒绝，而不只是加密
+        let config = VncDesConfig::new()
+            .with_rfc6143_strict(true)
+            .with_pad_byte(b' ');
+        let processor = VncDesProcessor::new(config);
+        let is_config_error =
+            |result: Result<_>| matches!(result, Err(VncDesError::ConfigError(_)));
+
+        assert!(is_config_error(processor.encrypt_password("pw").map(drop)));
+        assert!(is_config_error(
+            processor.decrypt_password(&block).map(drop)
+        ));
+        assert!(is_config_error(
+            processor.decrypt_password_bytes(&block).map(drop)
+        ));
+        assert!(is_config_error(
+            processor.decrypt_into(&block, &mut [0u8; 8]).map(drop)
+        ));
+        assert!(is_config_error(processor.encrypt_block(&block).map(drop)));
+        assert!(is_config_error(processor.decrypt_block(&block).map(drop)));
+        assert!(is_config_error(processor.encrypt_blocks(&block).map(drop)));
+        assert!(is_config_error(processor.decrypt_blocks(&block).map(drop)));
+        assert!(is_config_error(
+            processor.rotate_key(&block, &[0x11; 8]).map(drop)
+        ));
+    }
+
+    #[test]
+    fn test_provider_key_checked_against_config() {
+        use crate::key_provider::StaticKeyProvider;
+
+        // 配置中的默认密钥合法，但提供者返回的非标准密钥同样要经过严格模式检查
+        let mut processor =
+            VncDesProcessor::with_key_provider(StaticKeyProvider::new([1, 2, 3, 4, 5, 6, 7, 8]));
+        processor.set_config(VncDesConfig::new().with_rfc6143_strict(true));
+        assert!(processor.encrypt_password("password").is_err());
+        assert!(processor.decrypt_password(&[0u8; 8]).is_err());
+
+        let mut processor =
+            VncDesProcessor::with_key_provider(StaticKeyProvider::new(crate::REALVNC_DEFAULT_KEY));
+        processor.set_config(VncDesConfig::new().with_rfc6143_strict(true));
+        assert!(processor.encrypt_password("password").is_ok());
+        // 配置仍反映自身的密钥
+        assert_eq!(processor.config().encryption_key, TIGHTVNC_DEFAULT_KEY);
+    }
+
+    #[test]
+    fn test_authenticate_from_stored() {
+        let processor = VncDesProcessor::default();
+        let challenge: [u8; 16] = std::array::from_fn(|i| i as u8);
+
+        // dbd83cfd727a1458 是 "password" 使用默认密钥加密的结果
+        let response = processor
+            .authenticate_from_stored("dbd83cfd727a1458", &challenge)
+            .unwrap();
+        assert_eq!(response, VncAuth::respond("password", &challenge));
+        assert_eq!(
+            VncDesProcessor::to_hex_string(&response),
+            "b866924125c8eebb9debc1db61c538e2"
+        );
+
+        assert!(processor
+            .authenticate_from_stored("dbd83cfd", &challenge)
+            .is_err());
+        assert!(processor
+            .authenticate_from_stored("not hex!", &challenge)
+            .is_err());
+    }
+
+    #[test]
+    fn test_scheme_v1_fixture() {
+        let processor = VncDesProcessor::default();
+        assert_eq!(processor.scheme_version(), 1);
+        assert_eq!(SCHEME_VERSION, 1);
+
+        let fixture = include_str!("../../tests/fixtures/scheme_v1.txt");
+        let mut checked = 0;
+        for line in fixture
+            .lines()
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        {
+            let fields: Vec<&str> = line.split(' ').collect();
+            let (key, password, expected) = (fields[0], fields[1], fields[2]);
+
+            let processor = VncDesProcessor::with_hex_key(key).unwrap();
+            let encrypted = processor.encrypt_password(password).unwrap();
+            assert_eq!(
+                VncDesProcessor::to_hex_string(&encrypted),
+                expected,
+                "{}",
+                line
+            );
+            checked += 1;
+        }
+        assert!(checked > 0);
+    }
+
+    #[test]
+    fn test_versioned_round_trip() {
+        let processor = VncDesProcessor::default();
+        let tagged = processor.encrypt_versioned("password").unwrap();
+        assert_eq!(hex::encode(&tagged), "00000001dbd83cfd727a1458");
+        assert_eq!(processor.decrypt_versioned(&tagged).unwrap(), "password");
+
+        let mut future = tagged.clone();
+        future[3] = 2;
+        let err = processor.decrypt_versioned(&future).unwrap_err();
+        assert!(matches!(err, VncDesError::Unsupported(_)));
+
+        assert!(processor.decrypt_versioned(&tagged[4..]).is_err());
+    }
+
+    #[test]
+    fn test_encrypt_iter() {
+        let passwords = ["password", "", "secret", "verylongpassword"];
+        let mut expected = Vec::new();
+        let processor = VncDesProcessor::default();
+        for password in &passwords[..] {
+            expected.push(
+                processor
+                    .encrypt_password(password)
+                    .map(|e| VncDesProcessor::to_hex_string(&e))
+                    .ok(),
+            );
+        }
+
+        let results: Vec<Option<String>> =
+            processor.encrypt_iter(passwords).map(|r| r.ok()).collect();
+        assert_eq!(results, expected);
+        assert!(results[1].is_none());
+
+        // 惰性求值：只消费需要的部分
+        let owned: Vec<String> = (0..3).map(|i| format!("pw{}", i)).collect();
+        let first = processor
+            .encrypt_iter(owned.iter().map(String::as_str))
+            .next()
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            first,
+            VncDesProcessor::to_hex_string(&processor.encrypt_password("pw0").unwrap())
+        );
+
+        // 密钥被拒绝时只产生一个错误
+        let weak = VncDesProcessor::new(
+            VncDesConfig::new()
+                .with_key([0x80; 8])
+                .with_reject_weak_keys(true),
+        );
+        let results: Vec<_> = weak.encrypt_iter(["a", "b"]).collect();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].is_err());
+    }
+
+    #[test]
+    fn test_password_blocks() {
+        let processor = VncDesProcessor::default();
+
+        // 前8字节与单块加密结果一致
+        let encrypted = processor.encrypt_password_blocks("password1234").unwrap();
+        assert_eq!(encrypted.len(), 16);
+        assert_eq!(
+            &encrypted[..8],
+            processor.encrypt_password("password").unwrap()
+        );
+        assert_eq!(
+            processor.decrypt_password_blocks(&encrypted).unwrap(),
+            "password1234"
+        );
+
+        // 恰好8的倍数时不额外追加填充块
+        let encrypted = processor
+            .encrypt_password_blocks("abcdefgh12345678")
+            .unwrap();
+        assert_eq!(encrypted.len(), 16);
+        assert_eq!(
+            processor.decrypt_password_blocks(&encrypted).unwrap(),
+            "abcdefgh12345678"
+        );
+
+        assert_eq!(processor.encrypt_password_blocks("pw").unwrap().len(), 8);
+        assert!(processor.encrypt_password_blocks("").is_err());
+        assert!(processor.decrypt_password_blocks(&[]).is_err());
+        assert!(processor.decrypt_password_blocks(&[0u8; 12]).is_err());
+
+        let strict = VncDesProcessor::new(VncDesConfig::new().with_rfc6143_strict(true));
+        assert!(strict.encrypt_password_blocks("password").is_err());
+    }
+
+    #[test]
+    fn test_reject_weak_keys() {
+        let weak = [0x80; 8];
+        let mut processor = VncDesProcessor::with_key(weak);
+        let encrypted = processor.encrypt_password("password").unwrap();
+
+        processor.set_config(processor.config().clone().with_reject_weak_keys(true));
+        assert!(processor.encrypt_password("password").is_err());
+        assert!(processor.decrypt_password(&encrypted).is_err());
+
+        // 来自密钥提供者的弱密钥同样被拒绝
+        let mut processor = VncDesProcessor::with_key_provider(crate::StaticKeyProvider::new(weak));
+        processor.set_config(VncDesConfig::new().with_reject_weak_keys(true));
+        assert!(processor.encrypt_password("password").is_err());
+    }
+
+    #[test]
+    fn test_strict_mode_rejects_weak_keys() {
+        let weak = [0x80; 8];
+        let processor =
+            VncDesProcessor::new(VncDesConfig::new().with_strict_mode(true).with_key(weak));
+        assert!(processor.encrypt_password("password").is_err());
+        assert!(processor.encrypt_block(b"password").is_err());
+
+        let processor = VncDesProcessor::builder()
+            .strict_mode(true)
+            .key_provider(crate::StaticKeyProvider::new(weak))
+            .build()
+            .unwrap();
+        assert!(processor.encrypt_password("password").is_err());
+
+        // 非弱密钥不受影响
+        let processor = VncDesProcessor::new(VncDesConfig::new().with_strict_mode(true));
+        assert!(processor.encrypt_password("password").is_ok());
+    }
+
+    #[test]
+    fn test_key_parity_modes() {
+        let expected = VncDesProcessor::default()
+            .encrypt_password("password")
+            .unwrap();
+
+        // 校验位不参与DES运算，调整校验位不改变加密结果
+        let config = VncDesConfig::new().with_key_parity(ParityMode::SetOdd);
+        let processor = VncDesProcessor::new(config);
+        assert_eq!(processor.encrypt_password("password").unwrap(), expected);
+
+        let config = VncDesConfig::new().with_key_parity(ParityMode::Validate);
+        let processor = VncDesProcessor::new(config);
+        assert!(processor.encrypt_password("password").is_err());
+    }
+
+    #[test]
+    fn test_processor_builder() {
+        let processor = VncDesProcessor::builder()
+            .hex_key("0123456789abcdef")
+            .strict_mode(true)
+            .auto_truncate(false)
+            .build()
+            .unwrap();
+        assert!(processor.config().strict_mode);
+        assert!(processor.encrypt_password("toolongpassword").is_err());
+        assert_eq!(
+            processor.encrypt_password("test").unwrap(),
+            PasswordProcessor::encrypt_with_key(
+                "test",
+                &[0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef]
+            )
+            .unwrap()
+        );
+
+        // 密钥提供者优先于配置中的密钥
+        let processor = VncDesProcessor::builder()
+            .key_provider(crate::key_provider::StaticKeyProvider::new([7; 8]))
+            .build()
+            .unwrap();
+        assert_eq!(
+            processor.encrypt_password("test").unwrap(),
+            PasswordProcessor::encrypt_with_key("test", &[7; 8]).unwrap()
+        );
+
+        assert!(VncDesProcessor::builder()
+            .max_password_length(4)
+            .min_password_length(6)
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_verify_password_constant_time() {
+        let processor = VncDesProcessor::default();
+        let encrypted = processor.encrypt_password("password").unwrap();
+
+        assert!(processor
+            .verify_password_constant_time("password", &encrypted)
+            .unwrap());
+        assert!(!processor
+            .verify_password_constant_time("wrong", &encrypted)
+            .unwrap());
+        assert!(!processor
+            .verify_password_constant_time("password", &encrypted[..4])
+            .unwrap());
+    }
+
+    #[test]
+    fn test_security_report() {
+        let report = VncDesProcessor::default().security_report();
+        assert!(report.uses_default_key());
+        assert_eq!(report.key_rejected, None);
+        assert_eq!(report.key_entropy_bits, Some(0));
+        assert!(report.deterministic);
+        assert!(report.to_string().contains("使用了公开的VNC默认密钥"));
+
+        let report = VncDesProcessor::with_key([0x3a, 0x91, 0x5c, 0x07, 0xe2, 0x48, 0xbd, 0x16])
+            .security_report();
+        assert!(report.warnings.is_empty());
+        assert_eq!(report.key_entropy_bits, Some(56));
+        assert!(!report.key_from_provider);
+
+        // 密钥提供者无法提供密钥时熵未知
+        let provider = crate::key_provider::EnvKeyProvider::new("VNC_DES_TEST_SECURITY_REPORT");
+        let report = VncDesProcessor::with_key_provider(provider).security_report();
+        assert!(report.key_from_provider);
+        assert_eq!(report.key_entropy_bits, None);
+        assert_eq!(report.key_rejected, None);
+
+        // 被严格模式拒绝的弱密钥仍然要报告，而不是显示为没有问题
+        let config = VncDesConfig::new()
+            .with_key([0u8; 8])
+            .with_strict_mode(true);
+        let report = VncDesProcessor::new(config).security_report();
+        assert_eq!(report.warnings, [SecurityWarning::AllZeroKey]);
+        assert_eq!(report.key_entropy_bits, Some(0));
+        assert!(report.key_rejected.is_some());
+        let text = report.to_string();
+        assert!(text.contains("密钥被当前配置拒绝"));
+        assert!(!text.contains("未发现密钥问题"));
+    }
+
+    #[test]
+    fn test_encrypt_batch_with_progress() {
+        struct Counting(std::cell::RefCell<Vec<(usize, Option<usize>)>>);
+
+        impl Progress for Counting {
+            fn on_item(&self, done: usize, total: Option<usize>) {
+                self.0.borrow_mut().push((done, total));
+            }
+        }
+
+        let processor = VncDesProcessor::default();
+        let progress = Counting(Default::default());
+        let passwords = ["a", "b", "c"];
+
+        let encrypted = processor
+            .encrypt_batch_with_progress(&passwords, &progress)
+            .unwrap();
+        assert_eq!(encrypted, processor.encrypt_batch(&passwords).unwrap());
+        assert_eq!(
+            *progress.0.borrow(),
+            [(1, Some(3)), (2, Some(3)), (3, Some(3))]
+        );
+    }
+
+    #[test]
+    fn test_clear() {
+        let mut processor = VncDesProcessor::with_key([1, 2, 3, 4, 5, 6, 7, 8]);
+        processor.encrypt_password("password").unwrap();
+        assert!(processor.holds_sensitive_state());
+
+        processor.clear();
+        assert!(!processor.holds_sensitive_state());
+        assert_eq!(processor.config().encryption_key, [0u8; 8]);
+    }
+
+    #[cfg(feature = "debug-internals")]
+    #[test]
+    fn test_drop_without_clear_warns() {
+        use crate::logging::capture;
+
+        capture::install();
+        let warnings = || {
+            capture::records()
+                .iter()
+                .filter(|(level, msg)| *level == log::Level::Warn && msg.contains("clear()"))
+                .count()
+        };
+
+        let key = [0x3a, 0x91, 0x5c, 0x07, 0xe2, 0x48, 0xbd, 0x16];
+        drop(VncDesProcessor::with_key(key));
+        assert_eq!(warnings(), 1);
+
+        let mut processor = VncDesProcessor::with_key(key);
+        processor.clear();
+        drop(processor);
+        assert_eq!(warnings(), 1);
+
+        // 公开的默认密钥和库内部的临时处理器不产生警告
+        drop(VncDesProcessor::default());
+        drop(VncDesProcessor::new(VncDesConfig::for_product(
+            crate::VncProduct::RealVnc,
+        )));
+        PasswordProcessor::encrypt_with_key("password", &key).unwrap();
+        assert_eq!(warnings(), 1);
+    }
+
+    #[cfg(feature = "logging")]
+    #[test]
+    fn test_logging_omits_secrets() {
+        use crate::logging::capture;
+
+        capture::install();
+        let processor = VncDesProcessor::default();
+        let encrypted = processor.encrypt_password("secretpassword").unwrap();
+        let latin1 = VncDesProcessor::new(
+            VncDesConfig::new().with_password_encoding(PasswordEncoding::Latin1),
+        )
+        .encrypt_password("pé")
+        .unwrap();
+        assert!(processor.decrypt_password(&latin1).is_err());
+
+        let records = capture::records();
+        let messages: Vec<&str> = records.iter().map(|(_, msg)| msg.as_str()).collect();
+        assert!(messages.iter().any(|msg| msg.contains("DES密钥编排")));
+        assert!(messages.iter().any(|msg| msg.contains("已截断")));
+        assert!(messages.iter().any(|msg| msg.contains("不是有效的utf8")));
+
+        let key_hex = processor.config().key_as_hex();
+        for msg in messages {
+            assert!(!msg.contains("secret"), "{}", msg);
+            assert!(!msg.contains(&key_hex), "{}", msg);
+            assert!(!msg.contains(&VncDesProcessor::to_hex_string(&encrypted)));
+        }
+    }
+
+    #[test]
+    fn test_encrypt_encoded() {
+        let processor = VncDesProcessor::default();
+        let encoded = processor
+            .encrypt_encoded("password", &[Encoding::Hex, Encoding::Base64])
+            .unwrap();
+
+        assert_eq!(encoded.len(), 2);
+        assert_eq!(encoded[&Encoding::Hex], "dbd83cfd727a1458");
+        assert_eq!(
+            hex::decode(&encoded[&Encoding::Hex]).unwrap(),
+            base64::Engine::decode(
+                &base64::engine::general_purpose::STANDARD,
+                &encoded[&Encoding::Base64]
+            )
+            .unwrap()
+        );
+
+        assert!(processor
+            .encrypt_encoded("password", &[])
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_encrypt_batch_report() {
+        let processor = VncDesProcessor::default();
+        let passwords = ["secret", "other", "secret", "unique", "secret"];
+
+        let report = processor.encrypt_batch_report(&passwords).unwrap();
+        assert_eq!(report.ciphertexts.len(), 5);
+        assert_eq!(
+            report.ciphertexts[1],
+            processor.encrypt_password("other").unwrap()
+        );
+        assert!(report.has_reuse());
+        assert_eq!(report.reuse_clusters, vec![vec![0, 2, 4]]);
+
+        let report = processor.encrypt_batch_report(&["a", "b"]).unwrap();
+        assert!(!report.has_reuse());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_report_serde() {
+        let processor = VncDesProcessor::default();
+        let report = processor
+            .encrypt_batch_report(&["password", "password"])
+            .unwrap();
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(
+            json["ciphertexts"],
+            serde_json::json!(["dbd83cfd727a1458", "dbd83cfd727a1458"])
+        );
+        assert_eq!(json["reuse_clusters"], serde_json::json!([[0, 1]]));
+        assert_eq!(serde_json::from_value::<BatchReport>(json).unwrap(), report);
+
+        let security = processor.security_report();
+        let json = serde_json::to_value(&security).unwrap();
+        assert_eq!(json["warnings"], serde_json::json!(["default_key"]));
+        assert_eq!(
+            serde_json::from_value::<SecurityReport>(json).unwrap(),
+            security
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_rotate_json_value() {
+        let new_key = [0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08];
+        let processor = VncDesProcessor::default();
+
+        let mut doc = serde_json::json!([
+            { "host": "a", "password": "2f981dc548e09ec2" },
+            { "host": "b", "password": VncDesProcessor::to_hex_string(
+                &processor.encrypt_password("secret").unwrap()
+            ) },
+        ]);
+
+        let count = processor
+            .rotate_json_value(&mut doc, &new_key, "password")
+            .unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(doc[0]["host"], "a");
+
+        let rotated = VncDesProcessor::with_key(new_key);
+        for (index, expected) in ["test", "secret"].iter().enumerate() {
+            let hex_password = doc[index]["password"].as_str().unwrap();
+            let encrypted = VncDesProcessor::from_hex_string(hex_password).unwrap();
+            assert_eq!(rotated.decrypt_password(&encrypted).unwrap(), *expected);
+        }
+
+        // 缺少字段时不修改任何条目
+        let mut doc = serde_json::json!([
+            { "password": "2f981dc548e09ec2" },
+            { "other": "x" },
+        ]);
+        assert!(processor
+            .rotate_json_value(&mut doc, &new_key, "password")
+            .is_err());
+        assert_eq!(doc[0]["password"], "2f981dc548e09ec2");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_rotate_json_file() {
+        let new_key = [0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08];
+        let processor = VncDesProcessor::default();
+        let dir = std::env::temp_dir();
+        let path = dir.join(format!("vnc_des_rotate_{}.json", std::process::id()));
+
+        let original = r#"[{ "host": "a", "password": "2f981dc548e09ec2" }]"#;
+        std::fs::write(&path, original).unwrap();
+        assert_eq!(
+            processor
+                .rotate_json_file(&path, &new_key, "password")
+                .unwrap(),
+            1
+        );
+        let doc: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        let encrypted = VncDesProcessor::from_hex_string(doc[0]["password"].as_str().unwrap());
+        assert_eq!(
+            VncDesProcessor::with_key(new_key)
+                .decrypt_password(&encrypted.unwrap())
+                .unwrap(),
+            "test"
+        );
+
+        // 临时文件已被重命名，不会残留
+        let temp_prefix = format!(".{}.", path.file_name().unwrap().to_string_lossy());
+        assert!(!std::fs::read_dir(&dir).unwrap().any(|entry| entry
+            .unwrap()
+            .file_name()
+            .to_string_lossy()
+            .starts_with(&temp_prefix)));
+
+        // 轮换失败时原文件保持不变
+        let invalid = r#"[{ "host": "a" }]"#;
+        std::fs::write(&path, invalid).unwrap();
+        assert!(processor
+            .rotate_json_file(&path, &new_key, "password")
+            .is_err());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), invalid);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_from_reg_hex() {
+        let expected = vec![0x33, 0x48, 0x3f, 0xd5, 0x70, 0xcf, 0x86, 0x9b];
+
+        for input in [
+            r#""Password"=hex:33,48,3f,d5,70,cf,86,9b"#,
+            "hex:33,48,3F,D5,70,CF,86,9B",
+            "33, 48, 3f, d5, 70, cf, 86, 9b",
+            "\"Password\"=hex:33,48,3f,d5,\\\r\n  70,cf,86,9b\r\n",
+        ] {
+            assert_eq!(
+                VncDesProcessor::from_reg_hex(input).unwrap(),
+                expected,
+                "{}",
+                input
+            );
+        }
+
+        // 单个十六进制位
+        assert_eq!(
+            VncDesProcessor::from_reg_hex("HEX:3,48,3f,d5,70,cf,86,9b").unwrap()[0],
+            0x03
+        );
+
+        assert!(matches!(
+            VncDesProcessor::from_reg_hex("hex:33,48,3f,d5,70,cf,86"),
+            Err(VncDesError::InvalidPasswordFormat(_))
+        ));
+        assert!(matches!(
+            VncDesProcessor::from_reg_hex("hex:33,48,3f,d5,70,cf,86,9z"),
+            Err(VncDesError::HexDecodeError(_))
+        ));
+        assert!(matches!(
+            VncDesProcessor::from_reg_hex("hex:3348,3f,d5,70,cf,86,9b,00"),
+            Err(VncDesError::HexDecodeError(_))
+        ));
+    }
+
+    #[test]
+    fn test_is_valid_hex() {
+        assert!(VncDesProcessor::is_valid_hex("2f981dc548e09ec2"));
+        assert!(VncDesProcessor::is_valid_hex(" 2F981DC548E09EC2 "));
+        assert!(!VncDesProcessor::is_valid_hex("2f981dc5"));
+        assert!(!VncDesProcessor::is_valid_hex("2f981dc548e09ecz"));
+        assert!(!VncDesProcessor::is_valid_hex(""));
+    }
+
+    #[test]
+    fn test_password_processor() {
+        let password = "test";
+        let encrypted = PasswordProcessor::encrypt_with_default(password).unwrap();
+        let decrypted = PasswordProcessor::decrypt_with_default(&encrypted).unwrap();
+        assert_eq!(password, decrypted);
+    }
+
+    #[test]
+    fn test_raw_block() {
+        let key = [0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef];
+        let processor = VncDesProcessor::with_key(key);
+        // 含0字节和非ASCII字节的块原样处理，不做任何密码语义的检查
+        let block = [0x00, 0xff, 0x10, 0x80, 0x00, 0x00, 0x7f, 0x01];
+
+        let encrypted = processor.encrypt_block(&block).unwrap();
+        assert_eq!(encrypted, VncDesEngine::new().encrypt_block(&block, &key));
+        assert_eq!(processor.decrypt_block(&encrypted).unwrap(), block);
+
+        // 弱密钥检查与其他方法一致
+        let weak = VncDesProcessor::new(
+            VncDesConfig::new()
+                .with_key([0x80; 8])
+                .with_reject_weak_keys(true),
+        );
+        assert!(weak.encrypt_block(&block).is_err());
+    }
+
+    #[test]
+    fn test_ciphertext_equal() {
+        let processor = VncDesProcessor::default();
+        let a = processor.encrypt_password("password").unwrap();
+        // 超出最大长度的部分被截断，填充后的明文相同
+        let b = processor.encrypt_password("password123").unwrap();
+        let c = processor.encrypt_password("secret").unwrap();
+
+        assert!(VncDesProcessor::ciphertext_equal(&a, &b));
+        assert!(!VncDesProcessor::ciphertext_equal(&a, &c));
+        assert!(!VncDesProcessor::ciphertext_equal(&a, &a[..4]));
+
+        // 不同密钥下相同密码的密文不相等
+        let other = VncDesProcessor::with_key([0x01; 8]);
+        let d = other.encrypt_password("password").unwrap();
+        assert!(!VncDesProcessor::ciphertext_equal(&a, &d));
+    }
+
+    #[test]
+    fn test_verify_hex() {
+        assert!(PasswordProcessor::verify_hex_with_default("test", "2f981dc548e09ec2").unwrap());
+        assert!(PasswordProcessor::verify_hex_with_default("test", "2F981DC548E09EC2").unwrap());
+        assert!(!PasswordProcessor::verify_hex_with_default("tess", "2f981dc548e09ec2").unwrap());
+        assert!(PasswordProcessor::verify_hex_with_default("test", "2f981dc5").is_err());
+
+        let key = [0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08];
+        let encrypted = PasswordProcessor::encrypt_with_key("custom", &key).unwrap();
+        let hex_string = VncDesProcessor::to_hex_string(&encrypted);
+        assert!(PasswordProcessor::verify_hex_with_key("custom", &hex_string, &key).unwrap());
+        assert!(!PasswordProcessor::verify_hex_with_default("custom", &hex_string).unwrap());
+
+        // 同一处理器可重复校验
+        let processor = VncDesProcessor::with_key(key);
+        assert!(processor.verify_hex("custom", &hex_string).unwrap());
+        assert!(processor
+            .verify_hex("custom", &hex_string.to_uppercase())
+            .unwrap());
+        assert!(!processor.verify_hex("custon", &hex_string).unwrap());
+        assert!(processor.verify_hex("custom", "zz").is_err());
+    }
+
+    #[test]
+    fn test_custom_key() {
         let custom_key = [0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08];
         let password = "custom";
 
@@ -317,7 +2440,7 @@ mod tests {
             .with_auto_truncate(true)
             .with_max_password_length(4);
 
-        let mut processor = VncDesProcessor::new(config);
+        let processor = VncDesProcessor::new(config);
         let long_password = "verylongpassword";
 
         let encrypted = processor.encrypt_password(long_password).unwrap();
@@ -325,4 +2448,274 @@ mod tests {
 
         assert_eq!(decrypted, "very"); // 截断为前4个字符
     }
+
+    #[test]
+    fn test_utf16_round_trip() {
+        for encoding in [PasswordEncoding::Utf16Le, PasswordEncoding::Utf16Be] {
+            let config = VncDesConfig::new().with_password_encoding(encoding);
+            let processor = VncDesProcessor::new(config);
+
+            let encrypted = processor.encrypt_password("pw").unwrap();
+            assert_eq!(processor.decrypt_password(&encrypted).unwrap(), "pw");
+
+            let encrypted = processor.encrypt_password("密码").unwrap();
+            assert_eq!(processor.decrypt_password(&encrypted).unwrap(), "密码");
+
+            // 8字节只能容纳4个UTF-16码元
+            let encrypted = processor.encrypt_password("abcdef").unwrap();
+            assert_eq!(processor.decrypt_password(&encrypted).unwrap(), "abcd");
+        }
+
+        // 与UTF-8编码的结果不同
+        let utf16 = VncDesProcessor::new(
+            VncDesConfig::new().with_password_encoding(PasswordEncoding::Utf16Le),
+        )
+        .encrypt_password("pw")
+        .unwrap();
+        let utf8 = VncDesProcessor::default().encrypt_password("pw").unwrap();
+        assert_ne!(utf16, utf8);
+    }
+
+    #[test]
+    fn test_utf16_truncation_keeps_surrogate_pairs() {
+        for encoding in [PasswordEncoding::Utf16Le, PasswordEncoding::Utf16Be] {
+            // "abc😀" 编码后10字节，按UTF-16长度截断，整个代理对被舍弃
+            let config = VncDesConfig::new().with_password_encoding(encoding);
+            assert_eq!(config.truncate_password("abc😀"), "abc");
+            let processor = VncDesProcessor::new(config);
+            let encrypted = processor.encrypt_password("abc😀").unwrap();
+            assert_eq!(processor.decrypt_password(&encrypted).unwrap(), "abc");
+
+            // 最大长度足够时，8字节块的边界也不能拆开代理对
+            let config = VncDesConfig::new()
+                .with_password_encoding(encoding)
+                .with_max_password_length(16);
+            let processor = VncDesProcessor::new(config);
+            let encrypted = processor.encrypt_password("abc😀").unwrap();
+            assert_eq!(processor.decrypt_password(&encrypted).unwrap(), "abc");
+
+            // 4个汉字恰好8字节，UTF-8下会超过最大长度，UTF-16下不需要截断
+            let config = VncDesConfig::new()
+                .with_password_encoding(encoding)
+                .with_strict_mode(true)
+                .with_auto_truncate(false);
+            let processor = VncDesProcessor::new(config);
+            let encrypted = processor.encrypt_password("密码密码").unwrap();
+            assert_eq!(processor.decrypt_password(&encrypted).unwrap(), "密码密码");
+        }
+    }
+
+    #[test]
+    fn test_encoding_round_trip() {
+        let cases = [
+            (PasswordEncoding::Utf8, "密码"),
+            (PasswordEncoding::Latin1, "café"),
+            (PasswordEncoding::Latin1, "ÿü"),
+            (PasswordEncoding::Utf16Le, "pässwö"),
+            (PasswordEncoding::Utf16Be, "密码"),
+        ];
+
+        for (encoding, password) in cases {
+            let config = VncDesConfig::new().with_password_encoding(encoding);
+            let processor = VncDesProcessor::new(config);
+
+            let encrypted = processor.encrypt_password(password).unwrap();
+            let expected: String = match encoding {
+                PasswordEncoding::Utf16Le | PasswordEncoding::Utf16Be => {
+                    password.chars().take(4).collect()
+                }
+                _ => password.to_string(),
+            };
+            assert_eq!(
+                processor.decrypt_password(&encrypted).unwrap(),
+                expected,
+                "{}",
+                encoding
+            );
+        }
+    }
+
+    #[test]
+    fn test_decrypt_password_bytes() {
+        let processor = VncDesProcessor::default();
+        let encrypted = processor.encrypt_password("password").unwrap();
+        assert_eq!(
+            processor.decrypt_password_bytes(&encrypted).unwrap(),
+            b"password"
+        );
+
+        // 高位字节原样返回，不做UTF-8校验
+        let latin1 = VncDesConfig::new().with_password_encoding(PasswordEncoding::Latin1);
+        let encrypted = VncDesProcessor::new(latin1).encrypt_password("ÿü").unwrap();
+        assert_eq!(
+            processor.decrypt_password_bytes(&encrypted).unwrap(),
+            [0xff, 0xfc]
+        );
+        assert!(matches!(
+            processor.decrypt_password(&encrypted),
+            Err(VncDesError::DecryptionFailed(_))
+        ));
+
+        // UTF-16按码元去除填充
+        let utf16 = VncDesConfig::new().with_password_encoding(PasswordEncoding::Utf16Le);
+        let processor = VncDesProcessor::new(utf16);
+        let encrypted = processor.encrypt_password("ab").unwrap();
+        assert_eq!(
+            processor.decrypt_password_bytes(&encrypted).unwrap(),
+            [b'a', 0, b'b', 0]
+        );
+
+        assert!(processor.decrypt_password_bytes(&[0u8; 7]).is_err());
+    }
+
+    #[test]
+    fn test_decrypt_padding_semantics() {
+        let processor = VncDesProcessor::default();
+
+        // 8字节密码没有填充
+        let encrypted = processor.encrypt_password("12345678").unwrap();
+        assert_eq!(processor.decrypt_password(&encrypted).unwrap(), "12345678");
+        assert_eq!(
+            processor.decrypt_password_exact(&encrypted, 8).unwrap(),
+            "12345678"
+        );
+
+        // 结尾的0字节与填充无法区分
+        let with_nul = processor.encrypt_password("abc\0").unwrap();
+        assert_eq!(with_nul, processor.encrypt_password("abc").unwrap());
+        assert_eq!(processor.decrypt_password(&with_nul).unwrap(), "abc");
+        assert_eq!(
+            processor.decrypt_password_exact(&with_nul, 4).unwrap(),
+            "abc\0"
+        );
+        assert_eq!(
+            processor.decrypt_password_exact(&with_nul, 3).unwrap(),
+            "abc"
+        );
+
+        // 中间的0字节之后的内容只能按原始长度还原
+        let embedded = processor.encrypt_password("ab\0cd").unwrap();
+        assert_eq!(processor.decrypt_password(&embedded).unwrap(), "ab");
+        assert_eq!(
+            processor.decrypt_password_exact(&embedded, 5).unwrap(),
+            "ab\0cd"
+        );
+
+        assert!(matches!(
+            processor.decrypt_password_exact(&encrypted, 9),
+            Err(VncDesError::InvalidPasswordLength(_))
+        ));
+    }
+
+    #[test]
+    fn test_pad_byte() {
+        let processor = VncDesProcessor::new(VncDesConfig::new().with_pad_byte(b' '));
+        let mut engine = VncDesEngine::new();
+        let key = processor.config().encryption_key;
+
+        // 填充字节参与加密，与手工填充的明文块一致
+        let encrypted = processor.encrypt_password("a b").unwrap();
+        assert_eq!(encrypted, engine.encrypt_block(b"a b     ", &key));
+        assert_eq!(processor.decrypt_password(&encrypted).unwrap(), "a b");
+        assert_eq!(
+            processor.decrypt_password_bytes(&encrypted).unwrap(),
+            b"a b"
+        );
+
+        let mut out = [0u8; 8];
+        let len = processor
+            .decrypt_into(encrypted.as_slice().try_into().unwrap(), &mut out)
+            .unwrap();
+        assert_eq!((len, &out), (3, b"a b     "));
+
+        // 默认处理器不去除空格填充
+        assert_eq!(
+            VncDesProcessor::default()
+                .decrypt_password(&encrypted)
+                .unwrap(),
+            "a b     "
+        );
+
+        let blocks = processor.encrypt_password_blocks("password 1").unwrap();
+        assert_eq!(blocks.len(), 16);
+        assert_eq!(
+            processor.decrypt_password_blocks(&blocks).unwrap(),
+            "password 1"
+        );
+    }
+
+    #[test]
+    fn test_min_password_length() {
+        let config = VncDesConfig::new().with_min_password_length(4);
+        let processor = VncDesProcessor::new(config);
+
+        let result = processor.encrypt_password("abc");
+        assert!(matches!(result, Err(VncDesError::InvalidPasswordLength(_))));
+        assert!(processor.encrypt_password("abcd").is_ok());
+        assert!(processor.encrypt_password("abcde").is_ok());
+        assert!(processor.encrypt_password("").is_err());
+
+        // 长度按编码后的字节数计算：一个汉字在UTF-8下为3字节
+        assert!(processor.encrypt_password("密码").is_ok());
+        let err = processor.encrypt_password("密").unwrap_err();
+        assert!(err.to_string().contains("3 字节"), "{}", err);
+        assert!(processor.encrypt_password_blocks("密").is_err());
+    }
+
+    #[test]
+    fn test_process_password_borrows_without_truncation() {
+        let processor = VncDesProcessor::default();
+
+        let processed = processor.process_password("short").unwrap();
+        assert!(matches!(processed, Cow::Borrowed("short")));
+
+        let processed = processor.process_password("verylongpassword").unwrap();
+        assert!(matches!(processed, Cow::Owned(ref s) if s == "verylong"));
+    }
+
+    #[test]
+    fn test_truncate_multibyte_password() {
+        let processor = VncDesProcessor::default();
+
+        // 第8字节落在第3个汉字中间，截断不能切开字符
+        let processed = processor.process_password("密码密码密").unwrap();
+        assert_eq!(processed, "密码");
+        let encrypted = processor.encrypt_password("密码密码密").unwrap();
+        assert_eq!(processor.decrypt_password(&encrypted).unwrap(), "密码");
+
+        let processed = processor.process_password("ab😀😀").unwrap();
+        assert_eq!(processed, "ab😀");
+        assert_eq!(processor.process_password("abcdefg😀").unwrap(), "abcdefg");
+    }
+
+    #[test]
+    fn test_custom_key_provider() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        /// 记录调用次数的密钥提供者
+        #[derive(Debug, Default)]
+        struct CountingProvider {
+            calls: Arc<AtomicUsize>,
+        }
+
+        impl KeyProvider for CountingProvider {
+            fn key(&self) -> Result<[u8; 8]> {
+                self.calls.fetch_add(1, Ordering::SeqCst);
+                Ok([0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08])
+            }
+        }
+
+        let provider = CountingProvider::default();
+        let calls = Arc::clone(&provider.calls);
+        let processor = VncDesProcessor::with_key_provider(provider);
+
+        let encrypted = processor.encrypt_password("custom").unwrap();
+        let expected =
+            PasswordProcessor::encrypt_with_key("custom", &[1, 2, 3, 4, 5, 6, 7, 8]).unwrap();
+        assert_eq!(encrypted, expected);
+
+        let decrypted = processor.decrypt_password(&encrypted).unwrap();
+        assert_eq!(decrypted, "custom");
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
 }
@@ -0,0 +1,126 @@
+//! 密文文本编码模块
+//!
+//! 定义加密结果转换为文本时可选的编码格式
+
+use crate::error::{Result, VncDesError};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use std::fmt;
+
+/// 密文文本编码格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Encoding {
+    /// 小写十六进制（VNC配置文件中最常见的格式）
+    Hex,
+    /// 标准Base64字母表，带填充
+    Base64,
+}
+
+impl Encoding {
+    /// 所有支持的编码格式
+    pub const ALL: [Encoding; 2] = [Encoding::Hex, Encoding::Base64];
+
+    /// 获取编码名称
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Hex => "hex",
+            Self::Base64 => "base64",
+        }
+    }
+
+    /// 将字节编码为文本
+    pub fn encode(&self, bytes: &[u8]) -> String {
+        match self {
+            Self::Hex => hex::encode(bytes),
+            Self::Base64 => STANDARD.encode(bytes),
+        }
+    }
+
+    /// 将文本解码为字节（忽略首尾空白，十六进制不区分大小写）
+    pub fn decode(&self, text: &str) -> Result<Vec<u8>> {
+        let text = text.trim();
+        match self {
+            Self::Hex => hex::decode(text.to_lowercase()).map_err(|e| {
+                VncDesError::hex_decode_error(format!("无法解析十六进制字符串: {}", e))
+            }),
+            Self::Base64 => STANDARD.decode(text).map_err(|e| {
+                VncDesError::base64_decode_error(format!("无法解析Base64字符串: {}", e))
+            }),
+        }
+    }
+}
+
+/// 十六进制输出的大小写
+///
+/// 解码时不区分大小写，只影响编码结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum HexCase {
+    /// 小写（`hex::encode` 的默认格式）
+    #[default]
+    Lower,
+    /// 大写（常见于Windows注册表导出）
+    Upper,
+}
+
+impl HexCase {
+    /// 按当前大小写将字节编码为十六进制
+    pub fn encode(&self, bytes: &[u8]) -> String {
+        match self {
+            Self::Lower => hex::encode(bytes),
+            Self::Upper => hex::encode_upper(bytes),
+        }
+    }
+}
+
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode() {
+        let bytes = [0xdb, 0xd8, 0x3c, 0xfd, 0x72, 0x7a, 0x14, 0x58];
+        assert_eq!(Encoding::Hex.encode(&bytes), "dbd83cfd727a1458");
+        assert_eq!(Encoding::Base64.encode(&bytes), "29g8/XJ6FFg=");
+    }
+
+    #[test]
+    fn test_hex_case() {
+        let bytes = [0xdb, 0xd8, 0x3c, 0xfd, 0x72, 0x7a, 0x14, 0x58];
+        assert_eq!(HexCase::default(), HexCase::Lower);
+        assert_eq!(HexCase::Lower.encode(&bytes), "dbd83cfd727a1458");
+        assert_eq!(HexCase::Upper.encode(&bytes), "DBD83CFD727A1458");
+        assert_eq!(
+            Encoding::Hex
+                .decode(&HexCase::Upper.encode(&bytes))
+                .unwrap(),
+            bytes
+        );
+    }
+
+    #[test]
+    fn test_decode() {
+        let bytes = [0xdb, 0xd8, 0x3c, 0xfd, 0x72, 0x7a, 0x14, 0x58];
+        assert_eq!(Encoding::Hex.decode("DBD83CFD727A1458").unwrap(), bytes);
+        assert_eq!(Encoding::Base64.decode(" 29g8/XJ6FFg=\n").unwrap(), bytes);
+
+        // 缺少填充或使用URL安全字母表均视为错误
+        assert!(matches!(
+            Encoding::Base64.decode("29g8/XJ6FFg"),
+            Err(VncDesError::Base64DecodeError(_))
+        ));
+        assert!(matches!(
+            Encoding::Base64.decode("29g8_XJ6FFg="),
+            Err(VncDesError::Base64DecodeError(_))
+        ));
+    }
+}
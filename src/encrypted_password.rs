@@ -0,0 +1,187 @@
+//! 加密密码类型模块
+//!
+//! 以类型保证密文恰好为8字节，长度检查在构造时完成
+
+use crate::crypto::constant_time_eq;
+use crate::error::{Result, VncDesError};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+/// 8字节的VNC加密密码（单个DES块）
+///
+/// 从切片或十六进制字符串构造时检查长度，之后传给
+/// [`VncDesProcessor::decrypt`](crate::VncDesProcessor::decrypt)
+/// 不会再因长度错误而失败。比较使用常量时间实现。
+/// 启用 `serde` 特性时序列化为小写十六进制字符串，可直接嵌入应用自己的配置结构。
+///
+/// ```
+/// use vnc_des::{EncryptedPassword, VncDesProcessor};
+///
+/// let encrypted = EncryptedPassword::try_from("dbd83cfd727a1458")?;
+/// assert_eq!(encrypted.to_string(), "dbd83cfd727a1458");
+///
+/// let processor = VncDesProcessor::default();
+/// assert_eq!(processor.decrypt(&encrypted)?, "password");
+/// assert_eq!(processor.encrypt("password")?, encrypted);
+/// assert!(EncryptedPassword::try_from(&[0u8; 4][..]).is_err());
+/// # Ok::<(), vnc_des::VncDesError>(())
+/// ```
+#[derive(Debug, Clone, Copy, Eq)]
+pub struct EncryptedPassword([u8; 8]);
+
+impl EncryptedPassword {
+    /// 使用8字节密文创建
+    pub const fn new(bytes: [u8; 8]) -> Self {
+        Self(bytes)
+    }
+
+    /// 获取密文字节
+    pub fn as_bytes(&self) -> &[u8; 8] {
+        &self.0
+    }
+
+    /// 转换为密文字节
+    pub fn into_bytes(self) -> [u8; 8] {
+        self.0
+    }
+
+    /// 转换为小写十六进制字符串
+    pub fn to_hex(&self) -> String {
+        hex::encode(self.0)
+    }
+}
+
+impl From<[u8; 8]> for EncryptedPassword {
+    fn from(bytes: [u8; 8]) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<EncryptedPassword> for Vec<u8> {
+    fn from(encrypted: EncryptedPassword) -> Self {
+        encrypted.0.to_vec()
+    }
+}
+
+impl TryFrom<&[u8]> for EncryptedPassword {
+    type Error = VncDesError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self> {
+        let bytes: [u8; 8] = bytes.try_into().map_err(|_| {
+            VncDesError::invalid_password_format(format!(
+                "加密密码长度必须为8字节，实际长度: {}",
+                bytes.len()
+            ))
+        })?;
+        Ok(Self(bytes))
+    }
+}
+
+impl TryFrom<&str> for EncryptedPassword {
+    type Error = VncDesError;
+
+    /// 解析16字符的十六进制字符串（忽略首尾空白和大小写）
+    fn try_from(hex_string: &str) -> Result<Self> {
+        let bytes = crate::VncDesProcessor::from_hex_string(hex_string)?;
+        Self::try_from(bytes.as_slice())
+    }
+}
+
+impl FromStr for EncryptedPassword {
+    type Err = VncDesError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::try_from(s)
+    }
+}
+
+impl AsRef<[u8]> for EncryptedPassword {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl PartialEq for EncryptedPassword {
+    fn eq(&self, other: &Self) -> bool {
+        constant_time_eq(&self.0, &other.0)
+    }
+}
+
+impl Hash for EncryptedPassword {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+impl fmt::Display for EncryptedPassword {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_hex())
+    }
+}
+
+/// 序列化为十六进制字符串，反序列化规则同 [`FromStr`]
+#[cfg(feature = "serde")]
+impl serde::Serialize for EncryptedPassword {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_hex())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for EncryptedPassword {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Self, D::Error> {
+        let hex_string = String::deserialize(deserializer)?;
+        hex_string.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_construction() {
+        let bytes = [0xdb, 0xd8, 0x3c, 0xfd, 0x72, 0x7a, 0x14, 0x58];
+        let encrypted = EncryptedPassword::try_from(&bytes[..]).unwrap();
+        assert_eq!(encrypted.as_bytes(), &bytes);
+        assert_eq!(encrypted, EncryptedPassword::from(bytes));
+        assert_eq!(
+            " DBD83CFD727A1458 ".parse::<EncryptedPassword>().unwrap(),
+            encrypted
+        );
+        assert_eq!(Vec::from(encrypted), bytes.to_vec());
+
+        assert!(matches!(
+            EncryptedPassword::try_from(&bytes[..7]),
+            Err(VncDesError::InvalidPasswordFormat(_))
+        ));
+        assert!(EncryptedPassword::try_from("dbd83cfd").is_err());
+        assert!(EncryptedPassword::try_from("zzd83cfd727a1458").is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_hex() {
+        #[derive(serde::Serialize, serde::Deserialize)]
+        struct AppConfig {
+            vnc_password: EncryptedPassword,
+        }
+
+        let config = AppConfig {
+            vnc_password: "dbd83cfd727a1458".parse().unwrap(),
+        };
+        let json = serde_json::to_string(&config).unwrap();
+        assert_eq!(json, r#"{"vnc_password":"dbd83cfd727a1458"}"#);
+
+        let parsed: AppConfig =
+            serde_json::from_str(r#"{"vnc_password":"DBD83CFD727A1458"}"#).unwrap();
+        assert_eq!(parsed.vnc_password, config.vnc_password);
+        assert!(serde_json::from_str::<AppConfig>(r#"{"vnc_password":"dbd83cfd"}"#).is_err());
+    }
+}
@@ -28,17 +28,45 @@ pub enum VncDesError {
     #[error("十六进制解析错误: {0}")]
     HexDecodeError(String),
 
+    /// 十六进制字符串的长度（规范化后的字符数）错误，字符本身未被检查
+    #[error("十六进制字符串长度必须为{expected}字符，实际长度: {actual}")]
+    InvalidHexLength { expected: usize, actual: usize },
+
+    #[error("Base64解析错误: {0}")]
+    Base64DecodeError(String),
+
     #[error("配置错误: {0}")]
     ConfigError(String),
 
     #[error("I/O错误: {0}")]
     IoError(#[from] std::io::Error),
 
+    #[cfg(feature = "serde")]
     #[error("序列化错误: {0}")]
     SerializationError(#[from] serde_json::Error),
 
     #[error("十六进制编码错误: {0}")]
     HexEncodingError(#[from] hex::FromHexError),
+
+    #[error("不支持的操作: {0}")]
+    Unsupported(String),
+}
+
+/// 错误分类
+///
+/// 比逐个匹配 [`VncDesError`] 变体更粗粒度，便于调用方决定如何处理
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VncDesErrorKind {
+    /// 输入无效（密码、密钥、十六进制等格式或长度错误）
+    InvalidInput,
+    /// 加密或解密失败
+    Crypto,
+    /// 配置错误（包括配置序列化错误）
+    Config,
+    /// I/O错误
+    Io,
+    /// 请求的功能未编译进当前构建或尚未实现
+    Unsupported,
 }
 
 impl VncDesError {
@@ -76,4 +104,180 @@ impl VncDesError {
     pub fn hex_decode_error<T: Into<String>>(msg: T) -> Self {
         Self::HexDecodeError(msg.into())
     }
+
+    /// 创建一个十六进制长度错误
+    pub fn invalid_hex_length(expected: usize, actual: usize) -> Self {
+        Self::InvalidHexLength { expected, actual }
+    }
+
+    /// 创建一个Base64解析错误
+    pub fn base64_decode_error<T: Into<String>>(msg: T) -> Self {
+        Self::Base64DecodeError(msg.into())
+    }
+
+    /// 创建一个不支持的操作错误（功能未编译进当前构建或尚未实现）
+    pub fn unsupported<T: Into<String>>(msg: T) -> Self {
+        Self::Unsupported(msg.into())
+    }
+
+    /// 获取错误分类
+    pub fn kind(&self) -> VncDesErrorKind {
+        match self {
+            Self::InvalidPasswordLength(_)
+            | Self::InvalidKeyFormat(_)
+            | Self::InvalidPasswordFormat(_)
+            | Self::HexDecodeError(_)
+            | Self::InvalidHexLength { .. }
+            | Self::Base64DecodeError(_)
+            | Self::HexEncodingError(_) => VncDesErrorKind::InvalidInput,
+            Self::EncryptionFailed(_) | Self::DecryptionFailed(_) => VncDesErrorKind::Crypto,
+            Self::ConfigError(_) => VncDesErrorKind::Config,
+            #[cfg(feature = "serde")]
+            Self::SerializationError(_) => VncDesErrorKind::Config,
+            Self::IoError(_) => VncDesErrorKind::Io,
+            Self::Unsupported(_) => VncDesErrorKind::Unsupported,
+        }
+    }
+}
+
+/// 按变体和错误信息比较
+///
+/// `IoError`、`SerializationError` 包装的错误类型本身不支持比较，因此统一比较变体与
+/// [`Display`](std::fmt::Display) 输出；`IoError` 还会比较 [`ErrorKind`](std::io::ErrorKind)
+impl PartialEq for VncDesError {
+    fn eq(&self, other: &Self) -> bool {
+        if std::mem::discriminant(self) != std::mem::discriminant(other) {
+            return false;
+        }
+
+        if let (Self::IoError(a), Self::IoError(b)) = (self, other) {
+            if a.kind() != b.kind() {
+                return false;
+            }
+        }
+
+        self.to_string() == other.to_string()
+    }
+}
+
+impl Eq for VncDesError {}
+
+impl From<VncDesError> for std::io::Error {
+    /// 转换为标准I/O错误，便于在返回 `io::Result` 的代码中使用 `?`
+    fn from(err: VncDesError) -> Self {
+        use std::io::ErrorKind;
+
+        match err {
+            // 原始I/O错误直接透传
+            VncDesError::IoError(io_err) => io_err,
+            other => {
+                let kind = match &other {
+                    VncDesError::InvalidPasswordLength(_) | VncDesError::ConfigError(_) => {
+                        ErrorKind::InvalidInput
+                    }
+                    VncDesError::InvalidKeyFormat(_)
+                    | VncDesError::InvalidPasswordFormat(_)
+                    | VncDesError::HexDecodeError(_)
+                    | VncDesError::InvalidHexLength { .. }
+                    | VncDesError::Base64DecodeError(_)
+                    | VncDesError::HexEncodingError(_)
+                    | VncDesError::DecryptionFailed(_) => ErrorKind::InvalidData,
+                    #[cfg(feature = "serde")]
+                    VncDesError::SerializationError(_) => ErrorKind::InvalidData,
+                    VncDesError::Unsupported(_) => ErrorKind::Unsupported,
+                    VncDesError::EncryptionFailed(_) | VncDesError::IoError(_) => ErrorKind::Other,
+                };
+                std::io::Error::new(kind, other)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{self, ErrorKind};
+
+    #[test]
+    fn test_into_io_error_kind() {
+        let err: io::Error = VncDesError::hex_decode_error("bad hex").into();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        let err: io::Error = VncDesError::invalid_hex_length(16, 14).into();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        let err: io::Error = VncDesError::base64_decode_error("bad base64").into();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        let err: io::Error = VncDesError::invalid_password_length("too long").into();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert!(err.to_string().contains("too long"));
+    }
+
+    #[test]
+    fn test_kind() {
+        assert_eq!(
+            VncDesError::hex_decode_error("bad hex").kind(),
+            VncDesErrorKind::InvalidInput
+        );
+        assert_eq!(
+            VncDesError::decryption_failed("bad").kind(),
+            VncDesErrorKind::Crypto
+        );
+        assert_eq!(
+            VncDesError::config_error("bad").kind(),
+            VncDesErrorKind::Config
+        );
+        assert_eq!(
+            VncDesError::from(io::Error::new(ErrorKind::NotFound, "missing")).kind(),
+            VncDesErrorKind::Io
+        );
+    }
+
+    #[test]
+    fn test_unsupported() {
+        let err = VncDesError::unsupported("需要启用 qrcode 特性");
+        assert!(matches!(err, VncDesError::Unsupported(_)));
+        assert_eq!(err.kind(), VncDesErrorKind::Unsupported);
+        assert_ne!(err.kind(), VncDesErrorKind::Config);
+
+        let err: io::Error = err.into();
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+    }
+
+    #[test]
+    fn test_into_io_error_passthrough() {
+        let original = io::Error::new(ErrorKind::NotFound, "missing");
+        let err: io::Error = VncDesError::from(original).into();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+        assert_eq!(err.to_string(), "missing");
+    }
+
+    #[test]
+    fn test_eq() {
+        assert_eq!(
+            VncDesError::hex_decode_error("bad"),
+            VncDesError::hex_decode_error("bad")
+        );
+        assert_ne!(
+            VncDesError::hex_decode_error("bad"),
+            VncDesError::hex_decode_error("other")
+        );
+        // 信息相同但变体不同
+        assert_ne!(
+            VncDesError::config_error("bad"),
+            VncDesError::unsupported("bad")
+        );
+        assert_eq!(
+            VncDesError::invalid_hex_length(16, 14),
+            VncDesError::InvalidHexLength {
+                expected: 16,
+                actual: 14
+            }
+        );
+
+        let io = |kind| VncDesError::from(io::Error::new(kind, "missing"));
+        assert_eq!(io(ErrorKind::NotFound), io(ErrorKind::NotFound));
+        assert_ne!(io(ErrorKind::NotFound), io(ErrorKind::PermissionDenied));
+    }
 }
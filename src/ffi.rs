@@ -0,0 +1,165 @@
+//! C语言接口模块（需要启用 `ffi` 特性）
+//!
+//! 导出按VNC标准路径加解密单个8字节块的 `extern "C"` 函数，供C/C++编写的VNC
+//! 服务器或客户端直接链接。头文件为 `include/vnc_des.h`，修改接口后运行 `make header` 通过 `cbindgen` 重新生成。
+//!
+//! # ABI约定
+//!
+//! - 所有缓冲区由调用方分配和释放，函数只读取输入并写入调用方提供的输出缓冲区
+//! - 返回 [`VNC_DES_OK`]（0）表示成功，负数为错误码；出错时不写入输出缓冲区
+//! - 密钥为8字节的VNC固定密钥（如TightVNC的 `17 52 6b 06 23 4e 58 07`），
+//!   与 [`VncDesEngine::encrypt_block`] 相同，内部会反转每个字节的位序
+//! - 函数不持有全局状态，可在多个线程中同时调用
+//!
+//! 构建C可链接的库：
+//!
+//! ```text
+//! cargo rustc --lib --release --features ffi --crate-type staticlib
+//! ```
+
+use crate::crypto::scrub::scrub;
+use crate::crypto::VncDesEngine;
+use core::{ptr, slice};
+
+/// 成功
+pub const VNC_DES_OK: i32 = 0;
+/// 必需的指针参数为 `NULL`
+pub const VNC_DES_ERR_NULL_POINTER: i32 = -1;
+/// 长度无效（密码为空，或密文不是8字节）
+pub const VNC_DES_ERR_INVALID_LENGTH: i32 = -2;
+
+/// 加密VNC密码
+///
+/// 密码超过8字节时截断，不足时补0，与标准VNC实现一致。
+///
+/// # Safety
+///
+/// - `pw` 必须指向至少 `len` 个可读字节
+/// - `key` 必须指向8个可读字节
+/// - `out` 必须指向8个可写字节，且不与 `pw`、`key` 重叠
+#[no_mangle]
+pub unsafe extern "C" fn vnc_des_encrypt(
+    pw: *const u8,
+    len: usize,
+    key: *const u8,
+    out: *mut u8,
+) -> i32 {
+    if pw.is_null() || key.is_null() || out.is_null() {
+        return VNC_DES_ERR_NULL_POINTER;
+    }
+    if len == 0 {
+        return VNC_DES_ERR_INVALID_LENGTH;
+    }
+
+    let password = slice::from_raw_parts(pw, len);
+    let mut block = [0u8; 8];
+    let copy_len = len.min(8);
+    block[..copy_len].copy_from_slice(&password[..copy_len]);
+
+    let key = &*(key as *const [u8; 8]);
+    let encrypted = VncDesEngine::new().encrypt_block(&block, key);
+    scrub(&mut block);
+
+    ptr::copy_nonoverlapping(encrypted.as_ptr(), out, 8);
+    VNC_DES_OK
+}
+
+/// 解密VNC密码
+///
+/// 输出为补0后的8字节明文，第一个0字节之前的部分即密码（8字节密码没有结尾的0）。
+///
+/// # Safety
+///
+/// - `enc` 必须指向至少 `len` 个可读字节，且 `len` 必须为8
+/// - `key` 必须指向8个可读字节
+/// - `out` 必须指向8个可写字节，且不与 `enc`、`key` 重叠
+#[no_mangle]
+pub unsafe extern "C" fn vnc_des_decrypt(
+    enc: *const u8,
+    len: usize,
+    key: *const u8,
+    out: *mut u8,
+) -> i32 {
+    if enc.is_null() || key.is_null() || out.is_null() {
+        return VNC_DES_ERR_NULL_POINTER;
+    }
+    if len != 8 {
+        return VNC_DES_ERR_INVALID_LENGTH;
+    }
+
+    let mut block = [0u8; 8];
+    block.copy_from_slice(slice::from_raw_parts(enc, len));
+
+    let key = &*(key as *const [u8; 8]);
+    let mut decrypted = VncDesEngine::new().decrypt_block(&block, key);
+
+    ptr::copy_nonoverlapping(decrypted.as_ptr(), out, 8);
+    scrub(&mut decrypted);
+    VNC_DES_OK
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: [u8; 8] = [0x17, 0x52, 0x6b, 0x06, 0x23, 0x4e, 0x58, 0x07];
+
+    #[test]
+    fn test_round_trip() {
+        let mut encrypted = [0u8; 8];
+        let mut decrypted = [0u8; 8];
+
+        unsafe {
+            let pw = b"password123";
+            assert_eq!(
+                vnc_des_encrypt(pw.as_ptr(), pw.len(), KEY.as_ptr(), encrypted.as_mut_ptr()),
+                VNC_DES_OK
+            );
+            assert_eq!(encrypted, [0xdb, 0xd8, 0x3c, 0xfd, 0x72, 0x7a, 0x14, 0x58]);
+
+            assert_eq!(
+                vnc_des_decrypt(encrypted.as_ptr(), 8, KEY.as_ptr(), decrypted.as_mut_ptr()),
+                VNC_DES_OK
+            );
+        }
+        assert_eq!(&decrypted, b"password");
+
+        unsafe {
+            let pw = b"pw";
+            vnc_des_encrypt(pw.as_ptr(), pw.len(), KEY.as_ptr(), encrypted.as_mut_ptr());
+            vnc_des_decrypt(encrypted.as_ptr(), 8, KEY.as_ptr(), decrypted.as_mut_ptr());
+        }
+        assert_eq!(decrypted, [b'p', b'w', 0, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut out = [0xAAu8; 8];
+
+        unsafe {
+            assert_eq!(
+                vnc_des_encrypt(ptr::null(), 4, KEY.as_ptr(), out.as_mut_ptr()),
+                VNC_DES_ERR_NULL_POINTER
+            );
+            assert_eq!(
+                vnc_des_encrypt(b"pw".as_ptr(), 2, ptr::null(), out.as_mut_ptr()),
+                VNC_DES_ERR_NULL_POINTER
+            );
+            assert_eq!(
+                vnc_des_encrypt(b"pw".as_ptr(), 0, KEY.as_ptr(), out.as_mut_ptr()),
+                VNC_DES_ERR_INVALID_LENGTH
+            );
+            assert_eq!(
+                vnc_des_decrypt([0u8; 4].as_ptr(), 4, KEY.as_ptr(), out.as_mut_ptr()),
+                VNC_DES_ERR_INVALID_LENGTH
+            );
+            assert_eq!(
+                vnc_des_decrypt([0u8; 8].as_ptr(), 8, KEY.as_ptr(), ptr::null_mut()),
+                VNC_DES_ERR_NULL_POINTER
+            );
+        }
+
+        // 出错时不写入输出缓冲区
+        assert_eq!(out, [0xAAu8; 8]);
+    }
+}
@@ -0,0 +1,111 @@
+//! 密钥提供者模块
+//!
+//! 定义可插拔的密钥获取接口，便于与HSM、密钥管理服务等外部系统集成
+
+use crate::config::parse_hex_key;
+use crate::error::{Result, VncDesError};
+use std::fmt;
+
+/// 默认读取密钥的环境变量名
+pub const DEFAULT_KEY_ENV_VAR: &str = "VNC_DES_KEY";
+
+/// 密钥提供者
+///
+/// 处理器在每次加密/解密操作时调用 [`KeyProvider::key`] 获取密钥，
+/// 而不是持有固定的密钥数组，因此可以支持密钥轮换或远程密钥。
+///
+/// # 性能
+///
+/// 每次操作都会调用一次 `key()`。如果获取密钥的代价较高（例如网络请求），
+/// 应在提供者内部自行缓存。
+pub trait KeyProvider: fmt::Debug + Send + Sync {
+    /// 获取当前的8字节DES密钥
+    fn key(&self) -> Result<[u8; 8]>;
+}
+
+/// 静态密钥提供者（始终返回同一个密钥）
+#[derive(Debug, Clone)]
+pub struct StaticKeyProvider {
+    key: [u8; 8],
+}
+
+impl StaticKeyProvider {
+    /// 使用指定密钥创建提供者
+    pub fn new(key: [u8; 8]) -> Self {
+        Self { key }
+    }
+}
+
+impl KeyProvider for StaticKeyProvider {
+    fn key(&self) -> Result<[u8; 8]> {
+        Ok(self.key)
+    }
+}
+
+/// 环境变量密钥提供者
+///
+/// 每次调用时从环境变量读取16字符的十六进制密钥
+#[derive(Debug, Clone)]
+pub struct EnvKeyProvider {
+    var_name: String,
+}
+
+impl Default for EnvKeyProvider {
+    fn default() -> Self {
+        Self::new(DEFAULT_KEY_ENV_VAR)
+    }
+}
+
+impl EnvKeyProvider {
+    /// 使用指定的环境变量名创建提供者
+    pub fn new<T: Into<String>>(var_name: T) -> Self {
+        Self {
+            var_name: var_name.into(),
+        }
+    }
+
+    /// 获取环境变量名
+    pub fn var_name(&self) -> &str {
+        &self.var_name
+    }
+}
+
+impl KeyProvider for EnvKeyProvider {
+    fn key(&self) -> Result<[u8; 8]> {
+        let hex_key = std::env::var(&self.var_name).map_err(|_| {
+            VncDesError::config_error(format!("环境变量 {} 未设置或无效", self.var_name))
+        })?;
+        parse_hex_key(hex_key.trim())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_static_key_provider() {
+        let key = [1, 2, 3, 4, 5, 6, 7, 8];
+        let provider = StaticKeyProvider::new(key);
+        assert_eq!(provider.key().unwrap(), key);
+    }
+
+    #[test]
+    fn test_env_key_provider() {
+        let provider = EnvKeyProvider::new("VNC_DES_TEST_ENV_KEY_PROVIDER");
+
+        std::env::remove_var(provider.var_name());
+        assert!(provider.key().is_err());
+
+        std::env::set_var(provider.var_name(), "0123456789abcdef");
+        assert_eq!(
+            provider.key().unwrap(),
+            [0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef]
+        );
+
+        std::env::set_var(provider.var_name(), "0123");
+        assert!(provider.key().is_err());
+
+        std::env::remove_var(provider.var_name());
+    }
+}
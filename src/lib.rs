@@ -19,11 +19,12 @@
 //! ### 使用默认配置（VNC协议标准）
 //!
 //! ```rust
+//! # #[cfg(feature = "std")]
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
 //! use vnc_des::{VncDesProcessor, PasswordProcessor};
 //!
-//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
 //! // 方法1: 使用处理器实例
-//! let mut processor = VncDesProcessor::default();
+//! let processor = VncDesProcessor::default();
 //! let encrypted = processor.encrypt_password("password")?;
 //! let decrypted = processor.decrypt_password(&encrypted)?;
 //! assert_eq!(decrypted, "password");
@@ -34,36 +35,42 @@
 //! assert_eq!(decrypted, "password");
 //! # Ok(())
 //! # }
+//! # #[cfg(not(feature = "std"))]
+//! # fn main() {}
 //! ```
 //!
 //! ### 使用自定义密钥
 //!
 //! ```rust
+//! # #[cfg(feature = "std")]
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
 //! use vnc_des::{VncDesProcessor, VncDesConfig};
 //!
-//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
 //! // 方法1: 使用配置
 //! let config = VncDesConfig::new()
 //!     .with_hex_key("0123456789abcdef")?
 //!     .with_strict_mode(true);
 //!
-//! let mut processor = VncDesProcessor::new(config);
+//! let processor = VncDesProcessor::new(config);
 //! let encrypted = processor.encrypt_password("test")?;
 //!
 //! // 方法2: 直接指定密钥
 //! let custom_key = [0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08];
-//! let mut processor = VncDesProcessor::with_key(custom_key);
+//! let processor = VncDesProcessor::with_key(custom_key);
 //! let encrypted = processor.encrypt_password("test")?;
 //! # Ok(())
 //! # }
+//! # #[cfg(not(feature = "std"))]
+//! # fn main() {}
 //! ```
 //!
 //! ### 十六进制转换
 //!
 //! ```rust
+//! # #[cfg(feature = "std")]
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
 //! use vnc_des::{VncDesProcessor, PasswordProcessor};
 //!
-//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
 //! let encrypted = PasswordProcessor::encrypt_with_default("password")?;
 //! let hex_string = VncDesProcessor::to_hex_string(&encrypted);
 //! println!("加密密码: {}", hex_string);
@@ -72,20 +79,43 @@
 //! assert_eq!(encrypted, decoded);
 //! # Ok(())
 //! # }
+//! # #[cfg(not(feature = "std"))]
+//! # fn main() {}
+//! ```
+//!
+//! ### VNC认证挑战-响应
+//!
+//! ```rust
+//! # #[cfg(feature = "std")]
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use vnc_des::{VncAuth, VncDesProcessor};
+//!
+//! let challenge = [0u8; 16]; // 服务器发送的随机挑战
+//! let response = VncAuth::respond("password", &challenge);
+//!
+//! // 已保存的加密密码可以直接用于认证
+//! let processor = VncDesProcessor::default();
+//! assert_eq!(processor.authenticate_from_stored("dbd83cfd727a1458", &challenge)?, response);
+//! # Ok(())
+//! # }
+//! # #[cfg(not(feature = "std"))]
+//! # fn main() {}
 //! ```
 //!
 //! # 配置选项
 //!
 //! ```rust
+//! # #[cfg(feature = "std")]
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
 //! use vnc_des::{VncDesConfig, VncDesConfigBuilder};
 //!
-//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
 //! // 使用构建器模式
 //! let config = VncDesConfigBuilder::new()
-//!     .hex_key("17526b06234e5807")?  // 某些VNC实现的默认密钥（如TightVNC）
+//!     .hex_key("17526b06234e5807")   // 某些VNC实现的默认密钥（如TightVNC）
 //!     .strict_mode(false)            // 非严格模式
 //!     .auto_truncate(true)           // 自动截断长密码
 //!     .max_password_length(8)        // 最大密码长度
+//!     .min_password_length(1)        // 最小密码长度
 //!     .build()?;
 //!
 //! // 或者使用链式调用
@@ -94,16 +124,118 @@
 //!     .with_auto_truncate(false);
 //! # Ok(())
 //! # }
+//! # #[cfg(not(feature = "std"))]
+//! # fn main() {}
+//! ```
+//!
+//! # Cargo特性
+//!
+//! | 特性 | 默认 | 说明 |
+//! |------|------|------|
+//! | `std` | 是 | 标准库支持：处理器、配置、错误类型、十六进制转换等；其余特性均依赖此特性 |
+//! | `serde` | 是 | `VncDesConfig` 的序列化、JSON及配置文件读写、校验和；`EncryptedPassword`（十六进制字符串）、`BatchReport`、`SecurityReport` 等值类型的 `Serialize`/`Deserialize`；命令行工具依赖此特性 |
+//! | `cli` | 是 | 命令行工具 `vnc_des_tool` 的依赖（`clap`、`clap_complete`、`rpassword`），仅作为库使用时可关闭 |
+//! | `toml` | 否 | TOML格式的配置读写（`from_toml`、`to_toml`；`from_file`/`save_to_file` 按 `.toml` 扩展名选择格式） |
+//! | `qrcode` | 否 | 将加密结果渲染为二维码（`to_qr_svg`、`encrypt --qr`） |
+//! | `smallvec` | 否 | 多块加解密的栈上存储版本（`encrypt_blocks_small`、`decrypt_blocks_small`） |
+//! | `fast-keyschedule` | 否 | 使用预计算查找表生成DES子密钥，适合频繁更换密钥的场景（约增加32KB静态数据） |
+//! | `ffi` | 否 | C语言接口 `vnc_des_encrypt`/`vnc_des_decrypt`，头文件 `include/vnc_des.h` 由 `cbindgen` 生成（`make header`，见 [`ffi`] 模块） |
+//! | `wasm` | 否 | 通过 `wasm-bindgen` 导出 `encrypt`/`decrypt`，供浏览器使用（见 `examples/wasm`） |
+//! | `python` | 否 | 通过 `pyo3` 导出Python扩展模块（`encrypt`/`decrypt`/`verify`），使用 `maturin` 构建（见 [`python`] 模块） |
+//! | `async` | 否 | 预留的异步运行时支持 |
+//! | `zeroize` | 否 | 使用 `zeroize` crate 清零密钥、子密钥表及明文缓冲区（未启用时使用等效的volatile写入） |
+//! | `logging` | 否 | 通过 `log` crate 记录密钥编排、密码截断、解密结果解码失败等调试信息（只记录长度和结果，不记录密码或密钥） |
+//! | `debug-internals` | 否 | 暴露子密钥表等内部调试接口（`VncDesEngine::dump_schedule`/`subkeys`/`from_subkeys`、`config --compare-schedule`），并在处理器未调用 `clear()` 即被销毁时通过 `log` 记录警告 |
+//!
+//! 仅使用核心加解密功能时，可以通过 `default-features = false, features = ["std"]`
+//! 去掉 `serde`/`serde_json` 以及命令行工具的 `clap`/`clap_complete`/`rpassword` 依赖。
+//!
+//! # no_std
+//!
+//! 关闭 `std` 特性（`default-features = false`）后，库以 `no_std` 方式编译且不需要堆分配，
+//! 只保留 [`VncDesEngine`]（[`encrypt_block`](VncDesEngine::encrypt_block)、
+//! [`decrypt_block`](VncDesEngine::decrypt_block)、[`encrypt_challenge`](VncDesEngine::encrypt_challenge)）、VNC认证挑战-响应（[`VncAuth`]、
+//! [`VncChallengeProcessor`]）以及十六进制编码 [`to_hex_array`]，适用于嵌入式VNC客户端：
+//!
+//! ```rust
+//! use vnc_des::{to_hex_array, VncAuth, VncDesEngine};
+//!
+//! let mut engine = VncDesEngine::new();
+//! let key = [0x17, 0x52, 0x6b, 0x06, 0x23, 0x4e, 0x58, 0x07];
+//! let encrypted = engine.encrypt_block(b"password", &key);
+//! assert_eq!(engine.decrypt_block(&encrypted, &key), *b"password");
+//! assert_eq!(&to_hex_array(&encrypted), b"dbd83cfd727a1458");
+//!
+//! let response = VncAuth::respond("password", &[0u8; 16]);
+//! # let _ = response;
 //! ```
 
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+// 日志宏需要在其他模块之前声明
+#[macro_use]
+mod logging;
+
+#[cfg(feature = "std")]
 pub mod config;
 pub mod crypto;
+#[cfg(feature = "std")]
+pub mod encoding;
+#[cfg(feature = "std")]
+pub mod encrypted_password;
+#[cfg(feature = "std")]
 pub mod error;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "std")]
+pub mod key_provider;
+#[cfg(feature = "std")]
+pub mod passwd_file;
+#[cfg(feature = "std")]
+pub mod progress;
+#[cfg(feature = "python")]
+pub mod python;
+#[cfg(feature = "qrcode")]
+pub mod qr;
+#[cfg(feature = "std")]
+pub mod test_vectors;
+#[cfg(feature = "std")]
+pub mod vnc_file;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
 // 重新导出主要类型以便外部使用
-pub use config::{VncDesConfig, VncDesConfigBuilder, TIGHTVNC_DEFAULT_KEY};
-pub use crypto::{PasswordProcessor, VncDesEngine, VncDesProcessor};
-pub use error::{Result, VncDesError};
+#[cfg(feature = "serde")]
+pub use config::ConfigFormat;
+#[cfg(feature = "std")]
+pub use config::{
+    FieldDiff, KnownKey, ParityMode, PasswordEncoding, SecurityWarning, VncDesConfig,
+    VncDesConfigBuilder, VncProduct, REALVNC_DEFAULT_KEY, TIGHTVNC_DEFAULT_KEY,
+    ULTRAVNC_DEFAULT_KEY,
+};
+#[cfg(feature = "smallvec")]
+pub use crypto::BlockBuf;
+pub use crypto::{
+    constant_time_eq, to_hex_array, PreparedKey, VncAuth, VncChallengeProcessor, VncDesEngine,
+    CHALLENGE_SIZE,
+};
+#[cfg(feature = "std")]
+pub use crypto::{
+    BatchReport, PasswordProcessor, ProcessorBuilder, SecurityReport, VncDesProcessor,
+    SCHEME_VERSION,
+};
+#[cfg(feature = "std")]
+pub use encoding::{Encoding, HexCase};
+#[cfg(feature = "std")]
+pub use encrypted_password::EncryptedPassword;
+#[cfg(feature = "std")]
+pub use error::{Result, VncDesError, VncDesErrorKind};
+#[cfg(feature = "std")]
+pub use key_provider::{EnvKeyProvider, KeyProvider, StaticKeyProvider};
+#[cfg(feature = "std")]
+pub use progress::{NoProgress, Progress, StderrProgress};
+#[cfg(feature = "qrcode")]
+pub use qr::{to_qr_svg, to_qr_terminal};
 
 // 版本信息
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -120,11 +252,12 @@ pub fn name() -> &'static str {
 }
 
 /// 获取库的完整信息
+#[cfg(feature = "std")]
 pub fn info() -> String {
     format!("{} v{}", NAME, VERSION)
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
 
@@ -138,7 +271,7 @@ mod tests {
 
     #[test]
     fn test_basic_encryption() {
-        let mut processor = VncDesProcessor::default();
+        let processor = VncDesProcessor::default();
         let password = "test123";
 
         let encrypted = processor.encrypt_password(password).unwrap();
@@ -163,7 +296,7 @@ mod tests {
             .with_strict_mode(true)
             .with_auto_truncate(false);
 
-        let mut processor = VncDesProcessor::new(config);
+        let processor = VncDesProcessor::new(config);
         let password = "test";
 
         let encrypted = processor.encrypt_password(password).unwrap();
@@ -0,0 +1,90 @@
+//! 内部日志宏
+//!
+//! 启用 `logging` 特性时转发到 `log` crate，未启用时展开为空语句，没有任何运行时开销。
+//! 日志只记录长度和处理结果，不得包含明文密码或密钥字节。
+
+/// 记录 `debug` 级别日志（需要启用 `logging` 特性）
+// no_std 构建中只有密钥编排的 trace 日志
+#[cfg_attr(not(feature = "std"), allow(unused_macros))]
+#[cfg(feature = "logging")]
+macro_rules! log_debug {
+    ($($arg:tt)*) => {
+        log::debug!($($arg)*)
+    };
+}
+
+/// 记录 `debug` 级别日志（未启用 `logging` 特性，不产生任何代码）
+#[cfg_attr(not(feature = "std"), allow(unused_macros))]
+#[cfg(not(feature = "logging"))]
+macro_rules! log_debug {
+    ($($arg:tt)*) => {};
+}
+
+/// 记录 `trace` 级别日志（需要启用 `logging` 特性）
+#[cfg(feature = "logging")]
+macro_rules! log_trace {
+    ($($arg:tt)*) => {
+        log::trace!($($arg)*)
+    };
+}
+
+/// 记录 `trace` 级别日志（未启用 `logging` 特性，不产生任何代码）
+#[cfg(not(feature = "logging"))]
+macro_rules! log_trace {
+    ($($arg:tt)*) => {};
+}
+
+/// 测试用的日志捕获器
+///
+/// `log` 只允许设置一次全局日志器，所有需要检查日志的测试共用这一个实现。
+/// 测试并行运行，因此按线程记录，只返回当前线程产生的日志
+#[cfg(all(
+    test,
+    feature = "std",
+    any(feature = "logging", feature = "debug-internals")
+))]
+pub(crate) mod capture {
+    use std::sync::{Mutex, Once};
+    use std::thread::{self, ThreadId};
+
+    struct CaptureLogger(Mutex<Vec<(ThreadId, log::Level, String)>>);
+
+    impl log::Log for CaptureLogger {
+        fn enabled(&self, _: &log::Metadata) -> bool {
+            true
+        }
+
+        fn log(&self, record: &log::Record) {
+            self.0.lock().unwrap().push((
+                thread::current().id(),
+                record.level(),
+                record.args().to_string(),
+            ));
+        }
+
+        fn flush(&self) {}
+    }
+
+    static LOGGER: CaptureLogger = CaptureLogger(Mutex::new(Vec::new()));
+    static INIT: Once = Once::new();
+
+    /// 安装日志捕获器（可重复调用）
+    pub(crate) fn install() {
+        INIT.call_once(|| {
+            log::set_logger(&LOGGER).unwrap();
+            log::set_max_level(log::LevelFilter::Trace);
+        });
+    }
+
+    /// 获取当前线程已记录的日志
+    pub(crate) fn records() -> Vec<(log::Level, String)> {
+        LOGGER
+            .0
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(id, _, _)| *id == thread::current().id())
+            .map(|(_, level, msg)| (*level, msg.clone()))
+            .collect()
+    }
+}
@@ -0,0 +1,140 @@
+//! `vncpasswd` 二进制密码文件支持
+//!
+//! TightVNC/RealVNC/TigerVNC 的 `~/.vnc/passwd` 直接保存使用固定密钥加密后的8字节密文，
+//! 不是16进制文本
+
+use crate::crypto::VncDesProcessor;
+use crate::error::{Result, VncDesError};
+use std::io::Write;
+use std::path::Path;
+
+/// passwd文件中单个密码块的长度（字节）
+pub const PASSWD_BLOCK_SIZE: usize = 8;
+
+impl VncDesProcessor {
+    /// 解密passwd文件的原始内容（必须恰好为8字节）
+    pub fn decrypt_passwd_bytes(&self, content: &[u8]) -> Result<String> {
+        if content.len() < PASSWD_BLOCK_SIZE {
+            return Err(VncDesError::invalid_password_format(format!(
+                "passwd文件长度不足{}字节，实际长度: {}",
+                PASSWD_BLOCK_SIZE,
+                content.len()
+            )));
+        }
+
+        if content.len() > PASSWD_BLOCK_SIZE {
+            return Err(VncDesError::invalid_password_format(format!(
+                "passwd文件长度超过{}字节，实际长度: {}（可能包含第二个密码块，例如只读密码）",
+                PASSWD_BLOCK_SIZE,
+                content.len()
+            )));
+        }
+
+        self.decrypt_password(content)
+    }
+
+    /// 读取并解密 `vncpasswd` 生成的二进制密码文件
+    pub fn decrypt_passwd_file<P: AsRef<Path>>(&self, path: P) -> Result<String> {
+        let content = std::fs::read(path)?;
+        self.decrypt_passwd_bytes(&content)
+    }
+
+    /// 加密密码并写入 `vncpasswd` 格式的二进制文件（8字节原始密文，不是16进制）
+    ///
+    /// 文件已存在时覆盖。在Unix上文件权限设置为 `0600`（仅所有者可读写），
+    /// 已存在的文件也会被收紧为该权限；在Windows等其他平台上不修改权限，
+    /// 文件继承所在目录的ACL
+    pub fn write_passwd_file<P: AsRef<Path>>(&self, password: &str, path: P) -> Result<()> {
+        let encrypted = self.encrypt_password(password)?;
+        write_private_file(path.as_ref(), &encrypted)
+    }
+}
+
+/// 写入保存密码的文件（已存在时覆盖）
+///
+/// 在Unix上文件权限设置为 `0600`，已存在的文件也会被收紧；其他平台上不修改权限
+pub(crate) fn write_private_file(path: &Path, content: &[u8]) -> Result<()> {
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    let mut file = options.open(path)?;
+    #[cfg(unix)]
+    {
+        // mode 只在新建文件时生效，已存在的文件需要显式收紧权限
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+    }
+
+    file.write_all(content)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PASSWORD_BLOCK: [u8; 8] = [0xdb, 0xd8, 0x3c, 0xfd, 0x72, 0x7a, 0x14, 0x58];
+
+    #[test]
+    fn test_decrypt_passwd_bytes() {
+        let processor = VncDesProcessor::default();
+        assert_eq!(
+            processor.decrypt_passwd_bytes(&PASSWORD_BLOCK).unwrap(),
+            "password"
+        );
+
+        let err = processor
+            .decrypt_passwd_bytes(&PASSWORD_BLOCK[..4])
+            .unwrap_err();
+        assert!(err.to_string().contains("不足"));
+
+        let two_blocks = [PASSWORD_BLOCK, PASSWORD_BLOCK].concat();
+        let err = processor.decrypt_passwd_bytes(&two_blocks).unwrap_err();
+        assert!(err.to_string().contains("第二个密码块"));
+
+        // 16进制文本不是有效的passwd文件
+        assert!(processor.decrypt_passwd_bytes(b"dbd83cfd727a1458").is_err());
+    }
+
+    #[test]
+    fn test_write_passwd_file_round_trip() {
+        let path =
+            std::env::temp_dir().join(format!("vnc_des_write_passwd_{}", std::process::id()));
+        // 已存在的宽松权限文件会被覆盖并收紧
+        std::fs::write(&path, "previous content").unwrap();
+
+        let processor = VncDesProcessor::default();
+        processor.write_passwd_file("secret", &path).unwrap();
+
+        let content = std::fs::read(&path).unwrap();
+        assert_eq!(content.len(), PASSWD_BLOCK_SIZE);
+        assert_eq!(content, processor.encrypt_password("secret").unwrap());
+        assert_eq!(processor.decrypt_passwd_file(&path).unwrap(), "secret");
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_decrypt_passwd_file() {
+        let path = std::env::temp_dir().join(format!("vnc_des_passwd_{}", std::process::id()));
+        std::fs::write(&path, PASSWORD_BLOCK).unwrap();
+
+        let processor = VncDesProcessor::default();
+        assert_eq!(processor.decrypt_passwd_file(&path).unwrap(), "password");
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(processor.decrypt_passwd_file(&path).is_err());
+    }
+}
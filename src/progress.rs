@@ -0,0 +1,88 @@
+//! 进度报告模块
+//!
+//! 为批量等耗时较长的操作提供进度回调，使核心循环与进度显示解耦
+
+use std::io::Write;
+use std::time::Instant;
+
+/// 进度回调
+pub trait Progress {
+    /// 每处理完一项调用一次
+    ///
+    /// `done` 为已完成的项数（从1开始），`total` 为总项数（未知时为 `None`）
+    fn on_item(&self, done: usize, total: Option<usize>);
+}
+
+/// 不报告进度
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoProgress;
+
+impl Progress for NoProgress {
+    fn on_item(&self, _done: usize, _total: Option<usize>) {}
+}
+
+/// 在标准错误输出上显示进度百分比与速率
+#[derive(Debug, Clone)]
+pub struct StderrProgress {
+    start: Instant,
+}
+
+impl Default for StderrProgress {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StderrProgress {
+    /// 创建进度显示，速率从此刻开始计算
+    pub fn new() -> Self {
+        Self {
+            start: Instant::now(),
+        }
+    }
+
+    /// 格式化进度行
+    fn format_line(done: usize, total: Option<usize>, elapsed_secs: f64) -> String {
+        let rate = if elapsed_secs > 0.0 {
+            done as f64 / elapsed_secs
+        } else {
+            0.0
+        };
+
+        match total {
+            Some(total) if total > 0 => format!(
+                "进度: {}/{} ({:.1}%) {:.0} 项/秒",
+                done,
+                total,
+                done as f64 * 100.0 / total as f64,
+                rate
+            ),
+            _ => format!("进度: {} {:.0} 项/秒", done, rate),
+        }
+    }
+}
+
+impl Progress for StderrProgress {
+    fn on_item(&self, done: usize, total: Option<usize>) {
+        let line = Self::format_line(done, total, self.start.elapsed().as_secs_f64());
+        let mut stderr = std::io::stderr().lock();
+        let _ = write!(stderr, "\r{}", line);
+        if total == Some(done) {
+            let _ = writeln!(stderr);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_line() {
+        assert_eq!(
+            StderrProgress::format_line(5, Some(10), 1.0),
+            "进度: 5/10 (50.0%) 5 项/秒"
+        );
+        assert_eq!(StderrProgress::format_line(3, None, 0.0), "进度: 3 0 项/秒");
+    }
+}
@@ -0,0 +1,122 @@
+//! Python扩展模块（需要启用 `python` 特性）
+//!
+//! 通过 `pyo3` 导出 `encrypt`、`decrypt`、`verify` 三个函数，参数和返回值都是Python的
+//! `str`/`bool`，密钥为可选的16进制字符串（省略时使用VNC默认密钥）。错误以
+//! `vnc_des.VncDesError`（`ValueError` 的子类）抛出。
+//!
+//! 使用仓库根目录的 `pyproject.toml` 通过 maturin 构建：
+//!
+//! ```text
+//! pip install maturin
+//! maturin develop --release    # 安装到当前虚拟环境
+//! maturin build --release      # 生成wheel
+//! ```
+//!
+//! ```python
+//! import vnc_des
+//!
+//! assert vnc_des.encrypt("password") == "dbd83cfd727a1458"
+//! assert vnc_des.decrypt("dbd83cfd727a1458", key="17526b06234e5807") == "password"
+//! assert vnc_des.verify("password", "dbd83cfd727a1458")
+//! ```
+
+use crate::crypto::VncDesProcessor;
+use crate::error::Result;
+use pyo3::create_exception;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+create_exception!(
+    vnc_des,
+    VncDesError,
+    PyValueError,
+    "vnc_des 操作失败（密钥、密文格式无效或解密失败等）"
+);
+
+/// 加密密码，返回16进制密文
+#[pyfunction]
+#[pyo3(signature = (password, key = None))]
+fn encrypt(password: &str, key: Option<&str>) -> PyResult<String> {
+    encrypt_hex(password, key).map_err(to_py_err)
+}
+
+/// 解密16进制密文，返回明文密码
+#[pyfunction]
+#[pyo3(signature = (hex, key = None))]
+fn decrypt(hex: &str, key: Option<&str>) -> PyResult<String> {
+    decrypt_hex(hex, key).map_err(to_py_err)
+}
+
+/// 验证明文密码与16进制密文是否匹配（常量时间比较）
+#[pyfunction]
+#[pyo3(signature = (password, hex, key = None))]
+fn verify(password: &str, hex: &str, key: Option<&str>) -> PyResult<bool> {
+    verify_hex(password, hex, key).map_err(to_py_err)
+}
+
+/// Python模块入口，模块名与库名一致
+#[pymodule]
+fn vnc_des(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(encrypt, m)?)?;
+    m.add_function(wrap_pyfunction!(decrypt, m)?)?;
+    m.add_function(wrap_pyfunction!(verify, m)?)?;
+    m.add("VncDesError", m.py().get_type::<VncDesError>())?;
+    Ok(())
+}
+
+fn to_py_err(e: crate::error::VncDesError) -> PyErr {
+    VncDesError::new_err(e.to_string())
+}
+
+// 以下函数承载实际逻辑，不依赖Python解释器，以便在本机测试
+
+fn processor(key: Option<&str>) -> Result<VncDesProcessor> {
+    match key {
+        Some(hex_key) => VncDesProcessor::with_hex_key(hex_key.trim()),
+        None => Ok(VncDesProcessor::default()),
+    }
+}
+
+fn encrypt_hex(password: &str, key: Option<&str>) -> Result<String> {
+    let encrypted =
+        processor(key)?.use_once(|processor| processor.encrypt_password_array(password))?;
+    Ok(VncDesProcessor::to_hex_string(&encrypted))
+}
+
+fn decrypt_hex(hex: &str, key: Option<&str>) -> Result<String> {
+    processor(key)?
+        .use_once(|processor| processor.decrypt_password(&VncDesProcessor::from_hex_string(hex)?))
+}
+
+fn verify_hex(password: &str, hex: &str, key: Option<&str>) -> Result<bool> {
+    processor(key)?.use_once(|processor| processor.verify_hex(password, hex))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let encrypted = encrypt_hex("password", None).unwrap();
+        assert_eq!(encrypted, "dbd83cfd727a1458");
+        assert_eq!(
+            encrypt_hex("password", Some("17526b06234e5807")).unwrap(),
+            encrypted
+        );
+        assert_eq!(decrypt_hex(&encrypted, None).unwrap(), "password");
+        assert!(verify_hex("password", &encrypted, None).unwrap());
+        assert!(!verify_hex("wrong", &encrypted, None).unwrap());
+
+        let custom = encrypt_hex("password", Some("0123456789abcdef")).unwrap();
+        assert_ne!(custom, encrypted);
+        assert_eq!(
+            decrypt_hex(&custom, Some("0123456789abcdef")).unwrap(),
+            "password"
+        );
+
+        assert!(encrypt_hex("password", Some("1752")).is_err());
+        assert!(decrypt_hex("dbd83cfd", None).is_err());
+        assert!(verify_hex("password", "zz", None).is_err());
+    }
+}
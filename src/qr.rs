@@ -0,0 +1,49 @@
+//! 二维码输出模块（需要启用 `qrcode` 特性）
+//!
+//! 将加密结果渲染为二维码，便于通过扫码向设备下发VNC密码
+
+use crate::error::{Result, VncDesError};
+use qrcode::render::{svg, unicode};
+use qrcode::QrCode;
+
+/// 将字节编码为十六进制文本后生成二维码
+fn build_qr(bytes: &[u8]) -> Result<QrCode> {
+    QrCode::new(hex::encode(bytes))
+        .map_err(|e| VncDesError::invalid_password_format(format!("数据无法编码为二维码: {}", e)))
+}
+
+/// 将加密结果（十六进制形式）渲染为SVG二维码
+pub fn to_qr_svg(bytes: &[u8]) -> Result<String> {
+    let code = build_qr(bytes)?;
+    Ok(code.render::<svg::Color>().min_dimensions(200, 200).build())
+}
+
+/// 将加密结果（十六进制形式）渲染为终端可显示的二维码
+pub fn to_qr_terminal(bytes: &[u8]) -> Result<String> {
+    let code = build_qr(bytes)?;
+    Ok(code
+        .render::<unicode::Dense1x2>()
+        .dark_color(unicode::Dense1x2::Light)
+        .light_color(unicode::Dense1x2::Dark)
+        .build())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_qr_svg() {
+        let encrypted = [0x2f, 0x98, 0x1d, 0xc5, 0x48, 0xe0, 0x9e, 0xc2];
+        let svg = to_qr_svg(&encrypted).unwrap();
+        assert!(!svg.is_empty());
+        assert!(svg.contains("<svg"));
+    }
+
+    #[test]
+    fn test_to_qr_terminal() {
+        let encrypted = [0x2f, 0x98, 0x1d, 0xc5, 0x48, 0xe0, 0x9e, 0xc2];
+        let text = to_qr_terminal(&encrypted).unwrap();
+        assert!(text.lines().count() > 10);
+    }
+}
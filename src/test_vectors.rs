@@ -0,0 +1,113 @@
+//! 已知答案测试向量
+//!
+//! 收录各VNC实现以默认固定密钥保存密码时的密文，供下游集成方自检兼容性。
+//! 每条向量都在本模块的测试中验证。
+
+use crate::config::VncProduct;
+
+/// 一条已知答案测试向量
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TestVector {
+    /// 保存该密码的VNC实现
+    pub product: VncProduct,
+    /// 明文密码
+    pub plaintext: &'static str,
+    /// 16进制加密密钥（该实现的默认固定密钥）
+    pub hex_key: &'static str,
+    /// 期望的16进制密文
+    pub expected_hex: &'static str,
+}
+
+impl TestVector {
+    /// 以 `(明文, 16进制密钥, 16进制密文)` 形式返回
+    pub fn as_tuple(&self) -> (&'static str, &'static str, &'static str) {
+        (self.plaintext, self.hex_key, self.expected_hex)
+    }
+}
+
+const fn vector(
+    product: VncProduct,
+    plaintext: &'static str,
+    expected_hex: &'static str,
+) -> TestVector {
+    TestVector {
+        product,
+        plaintext,
+        hex_key: "17526b06234e5807",
+        expected_hex,
+    }
+}
+
+/// 已知答案测试向量表
+///
+/// 三种实现使用相同的默认密钥，因此相同明文的密文一致。超过8字节的密码按VNC惯例截断，
+/// `password123` 与 `password` 得到相同密文。
+///
+/// ```
+/// use vnc_des::test_vectors::VECTORS;
+/// use vnc_des::VncDesProcessor;
+///
+/// for vector in VECTORS {
+///     let processor = VncDesProcessor::with_hex_key(vector.hex_key)?;
+///     let encrypted = processor.encrypt_password(vector.plaintext)?;
+///     assert_eq!(VncDesProcessor::to_hex_string(&encrypted), vector.expected_hex);
+/// }
+/// # Ok::<(), vnc_des::VncDesError>(())
+/// ```
+pub const VECTORS: &[TestVector] = &[
+    vector(VncProduct::TightVnc, "test", "2f981dc548e09ec2"),
+    vector(VncProduct::TightVnc, "password", "dbd83cfd727a1458"),
+    vector(VncProduct::TightVnc, "password123", "dbd83cfd727a1458"),
+    vector(VncProduct::TightVnc, "12345678", "f0e43164f6c2e373"),
+    vector(VncProduct::RealVnc, "password", "dbd83cfd727a1458"),
+    vector(VncProduct::RealVnc, "secret", "2e2dbf576eb06c9e"),
+    vector(VncProduct::RealVnc, "vnc", "91bc75c18d3d85a7"),
+    vector(VncProduct::UltraVnc, "password", "dbd83cfd727a1458"),
+    vector(VncProduct::UltraVnc, "abc123", "275ea8d255b012f2"),
+    vector(VncProduct::UltraVnc, "pass word", "da026dbacf6cd323"),
+];
+
+/// 获取指定VNC实现的测试向量
+pub fn for_product(product: VncProduct) -> impl Iterator<Item = &'static TestVector> {
+    VECTORS
+        .iter()
+        .filter(move |vector| vector.product == product)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{VncDesConfig, VncDesProcessor};
+
+    #[test]
+    fn test_vectors() {
+        for vector in VECTORS {
+            // 向量中的密钥就是该产品的默认密钥
+            assert_eq!(vector.hex_key, hex::encode(vector.product.default_key()));
+
+            let processor = VncDesProcessor::new(VncDesConfig::for_product(vector.product));
+            let encrypted = processor.encrypt_password(vector.plaintext).unwrap();
+            assert_eq!(
+                VncDesProcessor::to_hex_string(&encrypted),
+                vector.expected_hex,
+                "{:?}",
+                vector
+            );
+
+            let decrypted = processor.decrypt_password(&encrypted).unwrap();
+            assert!(vector.plaintext.starts_with(&decrypted));
+        }
+    }
+
+    #[test]
+    fn test_for_product() {
+        for product in VncProduct::ALL {
+            assert!(for_product(product).count() >= 3);
+            assert!(for_product(product).all(|vector| vector.product == product));
+        }
+        assert_eq!(
+            VECTORS[0].as_tuple(),
+            ("test", "17526b06234e5807", "2f981dc548e09ec2")
+        );
+    }
+}
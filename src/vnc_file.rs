@@ -0,0 +1,263 @@
+//! `.vnc` 连接文件支持
+//!
+//! RealVNC/TigerVNC 的 `.vnc` 文件是INI格式的设置文件，其中 `Password=` 字段保存
+//! 使用固定密钥加密后的16进制密码
+
+use crate::crypto::VncDesProcessor;
+use crate::error::{Result, VncDesError};
+use crate::passwd_file::write_private_file;
+use std::path::Path;
+
+/// `.vnc` 文件中保存密码的字段名
+pub const PASSWORD_FIELD: &str = "Password";
+
+/// `.vnc` 文件中保存连接信息的节名
+pub const CONNECTION_SECTION: &str = "Connection";
+
+/// 判断INI行是否为指定字段（字段名不区分大小写）
+fn is_field_line(line: &str, field: &str) -> bool {
+    let line = line.trim();
+    !line.starts_with(';')
+        && !line.starts_with('#')
+        && line
+            .split_once('=')
+            .is_some_and(|(name, _)| name.trim().eq_ignore_ascii_case(field))
+}
+
+/// 在INI内容中查找指定字段的值（字段名不区分大小写，忽略节名和注释）
+fn find_ini_value<'a>(content: &'a str, field: &str) -> Option<&'a str> {
+    content.lines().find_map(|line| {
+        let line = line.trim();
+        if line.is_empty()
+            || line.starts_with(';')
+            || line.starts_with('#')
+            || line.starts_with('[')
+        {
+            return None;
+        }
+
+        let (name, value) = line.split_once('=')?;
+        name.trim()
+            .eq_ignore_ascii_case(field)
+            .then(|| value.trim())
+    })
+}
+
+impl VncDesProcessor {
+    /// 从 `.vnc` 文件内容中解密密码
+    pub fn decrypt_vnc_str(&self, content: &str) -> Result<String> {
+        let hex_password = find_ini_value(content, PASSWORD_FIELD).ok_or_else(|| {
+            VncDesError::invalid_password_format(format!(
+                "VNC连接文件中缺少 {} 字段",
+                PASSWORD_FIELD
+            ))
+        })?;
+
+        let encrypted = Self::from_hex_string(hex_password)?;
+        self.decrypt_password(&encrypted)
+    }
+
+    /// 生成包含加密密码的最小 `.vnc` 文件内容
+    pub fn vnc_file_content(&self, host: &str, port: u16, password: &str) -> Result<String> {
+        let encrypted = self.encrypt_password(password)?;
+        // IPv6地址需要加方括号以区分端口
+        let host = if host.contains(':') && !host.starts_with('[') {
+            format!("[{}]", host)
+        } else {
+            host.to_string()
+        };
+
+        Ok(format!(
+            "[{}]\nHost={}:{}\n{}={}\n",
+            CONNECTION_SECTION,
+            host,
+            port,
+            PASSWORD_FIELD,
+            Self::to_hex_string(&encrypted)
+        ))
+    }
+
+    /// 创建包含连接信息和加密密码的 `.vnc` 文件（已存在时覆盖）
+    ///
+    /// 与 [`write_passwd_file`](Self::write_passwd_file) 相同，在Unix上文件权限设置为 `0600`
+    pub fn write_vnc_file<P: AsRef<Path>>(
+        &self,
+        path: P,
+        host: &str,
+        port: u16,
+        password: &str,
+    ) -> Result<()> {
+        let content = self.vnc_file_content(host, port, password)?;
+        write_private_file(path.as_ref(), content.as_bytes())
+    }
+
+    /// 替换 `.vnc` 文件内容中的密码，其余内容保持不变
+    ///
+    /// 缺少 `Password` 字段时，插入到 `[Connection]` 节的开头（没有该节时追加到末尾）
+    pub fn update_vnc_password_str(&self, content: &str, password: &str) -> Result<String> {
+        let encrypted = self.encrypt_password(password)?;
+        let password_line = format!("{}={}", PASSWORD_FIELD, Self::to_hex_string(&encrypted));
+        let newline = if content.contains("\r\n") {
+            "\r\n"
+        } else {
+            "\n"
+        };
+
+        let mut lines: Vec<&str> = content.lines().collect();
+        let position = lines
+            .iter()
+            .position(|line| is_field_line(line, PASSWORD_FIELD));
+        match position {
+            Some(index) => lines[index] = &password_line,
+            None => {
+                let section = format!("[{}]", CONNECTION_SECTION);
+                match lines
+                    .iter()
+                    .position(|line| line.trim().eq_ignore_ascii_case(&section))
+                {
+                    Some(index) => lines.insert(index + 1, &password_line),
+                    None => lines.push(&password_line),
+                }
+            }
+        }
+
+        let mut updated = lines.join(newline);
+        updated.push_str(newline);
+        Ok(updated)
+    }
+
+    /// 就地更新 `.vnc` 文件中的密码，其余内容保持不变
+    ///
+    /// 在Unix上文件权限同时被收紧为 `0600`
+    pub fn update_vnc_file_password<P: AsRef<Path>>(&self, path: P, password: &str) -> Result<()> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)?;
+        let updated = self.update_vnc_password_str(&content, password)?;
+        write_private_file(path, updated.as_bytes())
+    }
+
+    /// 读取 `.vnc` 连接文件并解密其中的 `Password` 字段
+    pub fn decrypt_vnc_file<P: AsRef<Path>>(&self, path: P) -> Result<String> {
+        let content = std::fs::read_to_string(path)?;
+        self.decrypt_vnc_str(&content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_VNC_FILE: &str = "\
+[Connection]
+Host=192.168.1.10:5900
+password=dbd83cfd727a1458
+[Options]
+; 注释行
+UseLocalCursor=1
+FullScreen=0
+";
+
+    /// 保存密码的文件在Unix上应只有所有者可读写
+    fn assert_private(path: &Path) {
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        #[cfg(not(unix))]
+        let _ = path;
+    }
+
+    #[test]
+    fn test_decrypt_vnc_str() {
+        let processor = VncDesProcessor::default();
+        assert_eq!(
+            processor.decrypt_vnc_str(SAMPLE_VNC_FILE).unwrap(),
+            "password"
+        );
+
+        let err = processor
+            .decrypt_vnc_str("[Connection]\nHost=example.com\n")
+            .unwrap_err();
+        assert!(err.to_string().contains(PASSWORD_FIELD));
+
+        assert!(processor.decrypt_vnc_str("Password=xyz\n").is_err());
+    }
+
+    #[test]
+    fn test_decrypt_vnc_file() {
+        let path = std::env::temp_dir().join(format!("vnc_des_decrypt_{}.vnc", std::process::id()));
+        std::fs::write(&path, SAMPLE_VNC_FILE).unwrap();
+
+        let processor = VncDesProcessor::default();
+        assert_eq!(processor.decrypt_vnc_file(&path).unwrap(), "password");
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(processor.decrypt_vnc_file(&path).is_err());
+    }
+
+    #[test]
+    fn test_write_vnc_file() {
+        let path = std::env::temp_dir().join(format!("vnc_des_write_{}.vnc", std::process::id()));
+        let processor = VncDesProcessor::default();
+
+        processor
+            .write_vnc_file(&path, "192.168.1.10", 5901, "password")
+            .unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            content,
+            "[Connection]\nHost=192.168.1.10:5901\nPassword=dbd83cfd727a1458\n"
+        );
+        assert_eq!(processor.decrypt_vnc_file(&path).unwrap(), "password");
+        assert_private(&path);
+
+        std::fs::remove_file(&path).unwrap();
+
+        let content = processor.vnc_file_content("::1", 5900, "pw").unwrap();
+        assert!(content.contains("Host=[::1]:5900"));
+    }
+
+    #[test]
+    fn test_update_vnc_file_password() {
+        let path = std::env::temp_dir().join(format!("vnc_des_update_{}.vnc", std::process::id()));
+        std::fs::write(&path, SAMPLE_VNC_FILE).unwrap();
+        let processor = VncDesProcessor::default();
+
+        processor
+            .update_vnc_file_password(&path, "newpass")
+            .unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(processor.decrypt_vnc_str(&content).unwrap(), "newpass");
+
+        // 只有密码行发生变化
+        let changed: Vec<_> = SAMPLE_VNC_FILE
+            .lines()
+            .zip(content.lines())
+            .filter(|(old, new)| old != new)
+            .collect();
+        assert_eq!(changed.len(), 1);
+        assert_eq!(content.lines().count(), SAMPLE_VNC_FILE.lines().count());
+        assert_private(&path);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_update_vnc_password_str_inserts_missing_field() {
+        let processor = VncDesProcessor::default();
+
+        let content = "[Connection]\r\nHost=example.com\r\n";
+        let updated = processor
+            .update_vnc_password_str(content, "password")
+            .unwrap();
+        assert_eq!(
+            updated,
+            "[Connection]\r\nPassword=dbd83cfd727a1458\r\nHost=example.com\r\n"
+        );
+
+        let updated = processor.update_vnc_password_str("", "password").unwrap();
+        assert_eq!(processor.decrypt_vnc_str(&updated).unwrap(), "password");
+    }
+}
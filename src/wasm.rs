@@ -0,0 +1,57 @@
+//! WebAssembly接口模块（需要启用 `wasm` 特性）
+//!
+//! 通过 `wasm-bindgen` 导出字符串进、字符串出的加解密函数，供浏览器中的
+//! VNC客户端配置页面使用。错误以 JavaScript `Error` 抛出。
+//!
+//! ```text
+//! cargo rustc --lib --release --target wasm32-unknown-unknown --crate-type cdylib \
+//!     --no-default-features --features wasm
+//! wasm-bindgen --target web --out-dir pkg target/wasm32-unknown-unknown/release/vnc_des.wasm
+//! ```
+
+use crate::crypto::VncDesProcessor;
+use crate::error::Result;
+use wasm_bindgen::prelude::*;
+
+/// 使用16进制密钥加密密码，返回16进制密文
+#[wasm_bindgen]
+pub fn encrypt(password: &str, hex_key: &str) -> std::result::Result<String, JsError> {
+    encrypt_hex(password, hex_key).map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// 使用16进制密钥解密16进制密文，返回明文密码
+#[wasm_bindgen]
+pub fn decrypt(hex: &str, hex_key: &str) -> std::result::Result<String, JsError> {
+    decrypt_hex(hex, hex_key).map_err(|e| JsError::new(&e.to_string()))
+}
+
+// `JsError` 只能在wasm目标上构造，以下函数承载实际逻辑以便在本机测试
+
+fn encrypt_hex(password: &str, hex_key: &str) -> Result<String> {
+    let encrypted = VncDesProcessor::with_hex_key(hex_key)?
+        .use_once(|processor| processor.encrypt_password_array(password))?;
+    Ok(VncDesProcessor::to_hex_string(&encrypted))
+}
+
+fn decrypt_hex(hex: &str, hex_key: &str) -> Result<String> {
+    VncDesProcessor::with_hex_key(hex_key)?
+        .use_once(|processor| processor.decrypt_password(&VncDesProcessor::from_hex_string(hex)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let encrypted = encrypt_hex("password", "17526b06234e5807").unwrap();
+        assert_eq!(encrypted, "dbd83cfd727a1458");
+        assert_eq!(
+            decrypt_hex(&encrypted, "17526b06234e5807").unwrap(),
+            "password"
+        );
+
+        assert!(encrypt_hex("password", "1752").is_err());
+        assert!(decrypt_hex("dbd83cfd", "17526b06234e5807").is_err());
+    }
+}
@@ -0,0 +1,587 @@
+//! vnc_des_tool 命令行集成测试
+#![cfg(feature = "cli")]
+
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+use vnc_des::{PasswordProcessor, VncDesConfig, VncDesProcessor};
+
+/// 运行命令行工具，并将 `stdin` 写入其标准输入（不继承 `VNC_DES_KEY`）
+fn run_with_stdin(args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_vnc_des_tool"))
+        .args(args)
+        .env_remove("VNC_DES_KEY")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("无法启动 vnc_des_tool");
+
+    // 参数错误时进程可能在读取标准输入前就已退出，忽略由此产生的管道断开错误
+    if let Err(e) = child.stdin.take().unwrap().write_all(stdin.as_bytes()) {
+        assert_eq!(e.kind(), std::io::ErrorKind::BrokenPipe, "{}", e);
+    }
+
+    child.wait_with_output().unwrap()
+}
+
+/// 运行命令行工具，并设置指定的环境变量
+///
+/// 与 [`run_with_stdin`] 一样先清除开发环境中的 `VNC_DES_KEY`，避免影响测试结果
+fn run_with_env(args: &[&str], envs: &[(&str, &str)]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_vnc_des_tool"))
+        .args(args)
+        .env_remove("VNC_DES_KEY")
+        .envs(envs.iter().copied())
+        .stdin(Stdio::null())
+        .output()
+        .expect("无法启动 vnc_des_tool")
+}
+
+/// 运行命令行工具（标准输入为空）
+fn run(args: &[&str]) -> Output {
+    run_with_stdin(args, "")
+}
+
+#[test]
+fn test_key_from_stdin() {
+    let key = [0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef];
+    let expected =
+        VncDesProcessor::to_hex_string(&PasswordProcessor::encrypt_with_key("test", &key).unwrap());
+
+    let output = run_with_stdin(
+        &["--key-stdin", "encrypt", "test", "-q"],
+        "0123456789abcdef\n",
+    );
+
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), expected);
+}
+
+#[test]
+fn test_key_from_stdin_conflicts_with_key() {
+    let output = run_with_stdin(
+        &[
+            "--key-stdin",
+            "--key",
+            "0123456789abcdef",
+            "encrypt",
+            "test",
+        ],
+        "0123456789abcdef\n",
+    );
+
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_weak_key_warning() {
+    // 标准弱密钥 0101010101010101 的VNC位序形式
+    let output = run(&["--key", "8080808080808080", "encrypt", "test", "-q"]);
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("DES弱密钥"));
+    // 警告只输出到标准错误
+    assert_eq!(String::from_utf8_lossy(&output.stdout).lines().count(), 1);
+
+    let output = run(&["--key", "0123456789abcdef", "encrypt", "test", "-q"]);
+    assert!(output.stderr.is_empty());
+}
+
+#[test]
+fn test_key_from_stdin_invalid() {
+    let output = run_with_stdin(&["--key-stdin", "encrypt", "test", "-q"], "xyz\n");
+
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn test_validate_hex() {
+    let output = run(&["validate-hex", "2F98 1DC5 48E0 9EC2"]);
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "valid\n2f981dc548e09ec2\n"
+    );
+
+    let output = run(&["validate-hex", "2f981dc5"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stdout).starts_with("invalid"));
+
+    let output = run(&["validate-hex", "2f981dc548e09ecz"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stdout).starts_with("invalid"));
+}
+
+#[test]
+fn test_encrypt_min_password_length() {
+    let path = std::env::temp_dir().join(format!("vnc_des_min_len_{}.json", std::process::id()));
+    VncDesConfig::new()
+        .with_min_password_length(6)
+        .save_to_file(&path)
+        .unwrap();
+    let path_str = path.to_str().unwrap();
+
+    let output = run(&["--key-file", path_str, "encrypt", "abc", "-q"]);
+    assert!(!output.status.success());
+
+    let output = run(&["--key-file", path_str, "encrypt", "abc", "-q", "--no-min"]);
+    assert!(output.status.success());
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_encrypt_truncates_multibyte_password() {
+    let expected =
+        VncDesProcessor::to_hex_string(&PasswordProcessor::encrypt_with_default("密码").unwrap());
+
+    let output = run(&["encrypt", "密码密码密", "-v"]);
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("已截断为: '密码'"));
+    assert!(stdout.contains(&expected));
+    assert!(stdout.contains("加密解密一致"));
+}
+
+#[test]
+fn test_config_show_redact() {
+    let key = "abcdef0123456789";
+    let output = run(&["--key", key, "config", "--show", "--redact"]);
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!stdout.contains(key));
+    assert!(stdout.contains("\"key_redacted\": true"));
+}
+
+#[test]
+fn test_encrypt_multiple_passwords() {
+    let output = run(&["encrypt", "password", "test", "-q"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(
+        stdout.lines().collect::<Vec<_>>(),
+        ["dbd83cfd727a1458", "2f981dc548e09ec2"]
+    );
+
+    let output = run(&["encrypt", "password", "test"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("2个"));
+    assert!(stdout.contains("dbd83cfd727a1458  'password'"));
+
+    // 任一密码无效时不输出部分结果
+    let output = run(&["encrypt", "password", "", "-q"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn test_verify_exit_codes() {
+    let output = run(&["verify", "password", "dbd83cfd727a1458", "-q"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "true");
+
+    let output = run(&["verify", "wrong", "dbd83cfd727a1458", "-q"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "false");
+
+    let output = run(&["verify", "wrong", "dbd83cfd727a1458"]);
+    assert_eq!(output.status.code(), Some(1));
+
+    // 无效的16进制字符
+    let output = run(&["verify", "password", "dbd83cfd727a145z", "-q"]);
+    assert_eq!(output.status.code(), Some(2));
+
+    // 长度错误
+    let output = run(&["verify", "password", "dbd83cfd", "-q"]);
+    assert_eq!(output.status.code(), Some(2));
+
+    // 无效密钥
+    let output = run(&["--key", "xyz", "verify", "password", "dbd83cfd727a1458"]);
+    assert_eq!(output.status.code(), Some(2));
+
+    // 退出码在子命令帮助中列出
+    let output = run(&["verify", "--help"]);
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("退出码"));
+}
+
+#[test]
+fn test_verify_batch() {
+    let path = std::env::temp_dir().join(format!("vnc_des_batch_{}.tsv", std::process::id()));
+    let path_str = path.to_str().unwrap();
+
+    std::fs::write(
+        &path,
+        "# 审计列表\npassword\tdbd83cfd727a1458\n\ntest\t2F98:1DC5:48E0:9EC2\n",
+    )
+    .unwrap();
+    let output = run(&["verify", "--batch", path_str]);
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("第2行"));
+    assert!(stdout.contains("第4行"));
+    assert_eq!(stdout.lines().last(), Some("2 matched, 0 mismatched"));
+
+    std::fs::write(
+        &path,
+        "password\tdbd83cfd727a1458\nwrong\tdbd83cfd727a1458\n",
+    )
+    .unwrap();
+    let output = run(&["verify", "--batch", path_str, "-q"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout).trim(),
+        "1 matched, 1 mismatched"
+    );
+
+    // 缺少TAB或16进制无效的行为格式错误
+    std::fs::write(&path, "password dbd83cfd727a1458\ntest\tzz\n").unwrap();
+    let output = run(&["verify", "--batch", path_str, "-q"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout).trim(),
+        "0 matched, 0 mismatched, 2 invalid"
+    );
+
+    std::fs::remove_file(&path).unwrap();
+
+    let output = run(&["verify", "--batch", path_str]);
+    assert_eq!(output.status.code(), Some(2));
+    let output = run(&["verify", "--batch", path_str, "password"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn test_decrypt_in_file() {
+    let path = std::env::temp_dir().join(format!("vnc_des_in_file_{}", std::process::id()));
+    let path_str = path.to_str().unwrap();
+
+    // 二进制passwd文件
+    std::fs::write(&path, [0xdb, 0xd8, 0x3c, 0xfd, 0x72, 0x7a, 0x14, 0x58]).unwrap();
+    let output = run(&["decrypt", "--in-file", path_str, "-q"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "password");
+
+    // 16进制文本文件
+    std::fs::write(&path, "2F98 1DC5 48E0 9EC2\n").unwrap();
+    let output = run(&["decrypt", "--in-file", path_str, "-q"]);
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "test");
+
+    // Base64文本文件
+    std::fs::write(&path, "29g8/XJ6FFg=\n").unwrap();
+    let output = run(&["decrypt", "--in-file", path_str, "--format", "base64", "-q"]);
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "password");
+
+    // 既不是8字节也不是文本
+    std::fs::write(&path, [0xff; 16]).unwrap();
+    let output = run(&["decrypt", "--in-file", path_str, "-q"]);
+    assert_eq!(output.status.code(), Some(2));
+
+    let output = run(&["decrypt", "--in-file", path_str, "dbd83cfd727a1458"]);
+    assert_eq!(output.status.code(), Some(2));
+
+    std::fs::remove_file(&path).unwrap();
+    let output = run(&["decrypt", "--in-file", path_str]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn test_identify() {
+    let output = run(&["identify", "dbd83cfd727a1458", "-q"]);
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(
+        stdout.lines().collect::<Vec<_>>(),
+        [
+            "tightvnc\tpassword",
+            "realvnc\tpassword",
+            "ultravnc\tpassword"
+        ]
+    );
+
+    // 使用自定义密钥加密的密文不会解密出可打印ASCII
+    let output = run(&["identify", "0000000000000000", "-q"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+
+    let output = run(&["identify", "dbd83cfd"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn test_dump_vectors() {
+    let output = run(&["dump-vectors", "--count", "2"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "# key: 17526b06234e5807\npassword\tdbd83cfd727a1458\ntest\t2f981dc548e09ec2\n"
+    );
+
+    // 输出可直接用于批量验证（使用相同密钥）
+    let key = ["--key", "0123456789abcdef"];
+    let output = run(&[&key[..], &["dump-vectors", "-n", "12"]].concat());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.lines().count(), 13);
+    assert!(stdout.contains("pw000011\t"));
+
+    // 超过上限后生成的密码会超过8字节，直接拒绝
+    let output = run(&["dump-vectors", "-n", "1000001"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(output.stdout.is_empty());
+
+    let path = std::env::temp_dir().join(format!("vnc_des_dump_{}.tsv", std::process::id()));
+    std::fs::write(&path, stdout.as_bytes()).unwrap();
+    let batch = ["verify", "--batch", path.to_str().unwrap(), "-q"];
+    let output = run(&[&key[..], &batch[..]].concat());
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout).trim(),
+        "12 matched, 0 mismatched"
+    );
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[cfg(feature = "debug-internals")]
+#[test]
+fn test_compare_schedule() {
+    let key = "17526b06234e5807";
+    let output = run(&["config", "--compare-schedule", key, key]);
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("差异子密钥: 0/32"));
+
+    let output = run(&["config", "--compare-schedule", key, "0123456789abcdef"]);
+    assert!(output.status.success());
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("差异子密钥: 0/32"));
+}
+
+#[test]
+fn test_timing_safe_verify() {
+    let output = run(&[
+        "--timing-safe",
+        "verify",
+        "password",
+        "dbd83cfd727a1458",
+        "-q",
+    ]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "true");
+
+    let output = run(&["--timing-safe", "verify", "wrong", "dbd83cfd727a1458", "-q"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "false");
+
+    let output = run(&["--timing-safe", "verify", "password", "dbd83cfd", "-q"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn test_decrypt_compare() {
+    for timing_safe in [false, true] {
+        let mut args = vec!["decrypt", "dbd83cfd727a1458", "-q", "--compare"];
+        if timing_safe {
+            args.insert(0, "--timing-safe");
+        }
+
+        let mut matching = args.clone();
+        matching.push("password");
+        assert_eq!(run(&matching).status.code(), Some(0));
+
+        let mut mismatching = args;
+        mismatching.push("passwor");
+        assert_eq!(run(&mismatching).status.code(), Some(1));
+    }
+}
+
+#[test]
+fn test_key_from_env() {
+    let key = [0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef];
+    let expected =
+        VncDesProcessor::to_hex_string(&PasswordProcessor::encrypt_with_key("test", &key).unwrap());
+    let stdout = |output: &Output| String::from_utf8_lossy(&output.stdout).trim().to_string();
+
+    // 显式指定环境变量
+    let output = run_with_env(
+        &["--key-env", "VNC_DES_TEST_KEY", "encrypt", "test", "-q"],
+        &[("VNC_DES_TEST_KEY", "0123456789abcdef")],
+    );
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout(&output), expected);
+
+    // 未指定密钥选项时自动读取 VNC_DES_KEY
+    let output = run_with_env(
+        &["encrypt", "test", "-q"],
+        &[("VNC_DES_KEY", "0123456789abcdef")],
+    );
+    assert_eq!(stdout(&output), expected);
+
+    // --key 优先于环境变量
+    let output = run_with_env(
+        &["--key", "17526b06234e5807", "encrypt", "password", "-q"],
+        &[("VNC_DES_KEY", "0123456789abcdef")],
+    );
+    assert_eq!(stdout(&output), "dbd83cfd727a1458");
+
+    // 环境变量未设置或格式错误
+    let output = run_with_env(&["--key-env", "VNC_DES_TEST_UNSET", "encrypt", "test"], &[]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("VNC_DES_TEST_UNSET"));
+
+    let output = run_with_env(&["encrypt", "test"], &[("VNC_DES_KEY", "0123")]);
+    assert_eq!(output.status.code(), Some(2));
+
+    // 显式指定的 --product 优先于未显式选择的 VNC_DES_KEY，格式错误的环境变量也不影响
+    for key in ["0123456789abcdef", "0123"] {
+        let output = run_with_env(
+            &["--product", "tightvnc", "encrypt", "password", "-q"],
+            &[("VNC_DES_KEY", key)],
+        );
+        assert_eq!(output.status.code(), Some(0));
+        assert_eq!(stdout(&output), "dbd83cfd727a1458");
+    }
+
+    // 显式的 --key-env 仍优先于 --product
+    let output = run_with_env(
+        &[
+            "--key-env",
+            "VNC_DES_KEY",
+            "--product",
+            "realvnc",
+            "encrypt",
+            "test",
+            "-q",
+        ],
+        &[("VNC_DES_KEY", "0123456789abcdef")],
+    );
+    assert_eq!(stdout(&output), expected);
+}
+
+#[test]
+fn test_password_from_stdin() {
+    let stdout = |output: &Output| String::from_utf8_lossy(&output.stdout).trim().to_string();
+
+    let output = run_with_stdin(&["encrypt", "--stdin", "-q"], "password\n");
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout(&output), "dbd83cfd727a1458");
+
+    let output = run_with_stdin(&["encrypt", "-", "-q"], "password\n");
+    assert_eq!(stdout(&output), "dbd83cfd727a1458");
+
+    let output = run_with_stdin(
+        &["verify", "--stdin", "dbd83cfd727a1458", "-q"],
+        "password\n",
+    );
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout(&output), "true");
+
+    let output = run_with_stdin(&["verify", "-", "dbd83cfd727a1458", "-q"], "passwor\n");
+    assert_eq!(output.status.code(), Some(1));
+
+    // 密钥与密码不能同时从标准输入读取
+    let output = run_with_stdin(&["--key-stdin", "encrypt", "-"], "0123456789abcdef\n");
+    assert_eq!(output.status.code(), Some(2));
+
+    // 标准输入只能读取一个密码
+    let output = run_with_stdin(&["encrypt", "-", "-", "-q"], "password\nsecret\n");
+    assert_eq!(output.status.code(), Some(2));
+    assert!(output.stdout.is_empty());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("标准输入"));
+
+    let output = run_with_stdin(&["encrypt", "-", "test", "-q"], "password\n");
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout(&output), "dbd83cfd727a1458\n2f981dc548e09ec2");
+}
+
+#[test]
+fn test_base64_format() {
+    let output = run(&["encrypt", "password", "-q", "--format", "base64"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout).trim(),
+        "29g8/XJ6FFg="
+    );
+
+    let output = run(&["decrypt", "29g8/XJ6FFg=", "-q", "--format", "base64"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "password");
+
+    // 十六进制密文不是有效的8字节Base64密文
+    let output = run(&["decrypt", "dbd83cfd727a1458", "-q", "--format", "base64"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Base64"));
+}
+
+#[test]
+fn test_hex_case_upper() {
+    let output = run(&["encrypt", "password", "-q", "--hex-case", "upper"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout).trim(),
+        "DBD83CFD727A1458"
+    );
+
+    // 解密不区分大小写
+    let output = run(&["decrypt", "DBD83CFD727A1458", "-q"]);
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "password");
+}
+
+#[cfg(not(feature = "qrcode"))]
+#[test]
+fn test_qr_unsupported_without_feature() {
+    let output = run(&["encrypt", "password", "--qr"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("不支持的操作"));
+}
+
+#[test]
+fn test_diagnose() {
+    let key = "0123456789abcdef";
+    let output = run(&["--key", key, "diagnose", "password"]);
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let config = VncDesConfig::new().with_hex_key(key).unwrap();
+    for field in [
+        "version:",
+        "features:",
+        "config.strict_mode:",
+        "encrypted_hex:",
+        "schedule_fingerprint:",
+        "round_trip: ok",
+    ] {
+        assert!(stdout.contains(field), "missing {}: {}", field, stdout);
+    }
+    assert!(stdout.contains(&config.key_fingerprint()));
+    assert!(!stdout.contains(key));
+
+    let output = run(&["--key", key, "diagnose", "password", "--json"]);
+    assert!(output.status.success());
+
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let processor = VncDesProcessor::new(config.clone());
+    assert_eq!(
+        report["encrypted_hex"],
+        VncDesProcessor::to_hex_string(&processor.encrypt_password("password").unwrap())
+    );
+    assert_eq!(report["key_fingerprint"], config.key_fingerprint());
+    assert_eq!(
+        report["schedule_fingerprint"],
+        processor.key_schedule_fingerprint().unwrap()
+    );
+    assert_eq!(report["config"]["key_redacted"], true);
+    assert_eq!(report["round_trip"], true);
+    assert!(report["version"].is_string());
+}
+
+#[test]
+fn test_completions() {
+    let output = run(&["completions", "bash"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("_vnc_des_tool"));
+    assert!(stdout.contains("encrypt"));
+
+    let output = run(&["completions", "tcsh"]);
+    assert_eq!(output.status.code(), Some(2));
+}
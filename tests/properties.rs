@@ -0,0 +1,64 @@
+//! 基于 proptest 的加解密往返属性测试
+#![cfg(feature = "std")]
+
+use proptest::prelude::*;
+use vnc_des::{VncDesConfig, VncDesProcessor};
+
+/// 1到8字节、不含0字节的ASCII密码
+fn ascii_password() -> impl Strategy<Value = String> {
+    prop::collection::vec(1u8..0x80, 1..=8)
+        .prop_map(|bytes| String::from_utf8(bytes).expect("ASCII总是有效的UTF-8"))
+}
+
+proptest! {
+    #[test]
+    fn encrypt_decrypt_round_trip(password in ascii_password(), key in any::<[u8; 8]>()) {
+        let processor = VncDesProcessor::with_key(key);
+
+        let encrypted = processor.encrypt_password(&password).unwrap();
+        prop_assert_eq!(encrypted.len(), 8);
+        prop_assert_eq!(processor.decrypt_password(&encrypted).unwrap(), password);
+    }
+
+    #[test]
+    fn decrypt_exact_keeps_nul_bytes(
+        bytes in prop::collection::vec(0u8..0x80, 1..=8),
+        key in any::<[u8; 8]>(),
+    ) {
+        // 首字节为0时密码在编码层面等价于空密码，不在此测试范围内
+        prop_assume!(bytes[0] != 0);
+        let password = String::from_utf8(bytes).unwrap();
+        let processor = VncDesProcessor::with_key(key);
+
+        let encrypted = processor.encrypt_password(&password).unwrap();
+        prop_assert_eq!(
+            processor.decrypt_password_exact(&encrypted, password.len()).unwrap(),
+            password.as_str()
+        );
+
+        // 不知道原始长度时，第一个0字节之后视为填充
+        let expected = password.split('\0').next().unwrap();
+        prop_assert_eq!(processor.decrypt_password(&encrypted).unwrap(), expected);
+    }
+
+    #[test]
+    fn truncation_keeps_char_boundaries(password in "\\PC{1,12}", max in 1usize..=16) {
+        let processor = VncDesProcessor::new(VncDesConfig::new().with_max_password_length(max));
+
+        let truncated = processor.config().truncate_password(&password);
+        prop_assert!(truncated.len() <= max);
+        prop_assert!(password.starts_with(truncated));
+        // 截断时最多舍弃一个字符的字节数（不超过3字节）
+        prop_assert!(password.len() <= max || max - truncated.len() < 4);
+        prop_assert!(processor.encrypt_password(&password).is_ok());
+    }
+
+    #[test]
+    fn hex_round_trip(bytes in any::<[u8; 8]>()) {
+        let hex = VncDesProcessor::to_hex_string(&bytes);
+        prop_assert_eq!(VncDesProcessor::from_hex_string(&hex).unwrap(), bytes.to_vec());
+
+        let upper = VncDesProcessor::to_hex_string_upper(&bytes);
+        prop_assert_eq!(VncDesProcessor::from_hex_string(&upper).unwrap(), bytes.to_vec());
+    }
+}
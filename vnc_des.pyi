@@ -0,0 +1,15 @@
+"""VNC DES密码加解密（Rust实现，由 vnc_des crate 的 python 特性构建）"""
+
+from typing import Optional
+
+class VncDesError(ValueError):
+    """密钥、密文格式无效或解密失败等"""
+
+def encrypt(password: str, key: Optional[str] = None) -> str:
+    """加密密码，返回16进制密文；key 为16进制密钥，省略时使用VNC默认密钥"""
+
+def decrypt(hex: str, key: Optional[str] = None) -> str:
+    """解密16进制密文，返回明文密码"""
+
+def verify(password: str, hex: str, key: Optional[str] = None) -> bool:
+    """验证明文密码与16进制密文是否匹配（常量时间比较）"""